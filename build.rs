@@ -0,0 +1,54 @@
+/// Generates the registry of all scaffolded puzzles (`src/bin/YYYY-DD.rs`), which compiles every solution into the
+/// runner binary so that `cargo all` and `cargo time` can execute them in-process.
+use std::{env, fs, path::Path};
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    println!("cargo:rerun-if-changed={}", bin_dir.display());
+
+    let mut puzzles: Vec<(u16, u8)> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| {
+                    let name = entry.file_name().into_string().ok()?;
+                    let (year, day) = name.strip_suffix(".rs")?.split_once('-')?;
+                    if year.len() != 4 || day.len() != 2 {
+                        return None;
+                    }
+                    let year = year.parse().ok().filter(|year| *year >= 2015)?;
+                    let day = day.parse().ok().filter(|day| (1..=25).contains(day))?;
+                    Some((year, day))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    puzzles.sort_unstable();
+
+    let mut registry = String::new();
+
+    for (year, day) in &puzzles {
+        let path = bin_dir.join(format!("{year}-{day:02}.rs"));
+        registry.push_str(&format!(
+            "#[path = {:?}]\n#[allow(dead_code, unused_variables)]\nmod day_{year}_{day:02};\n\n",
+            path.display().to_string()
+        ));
+    }
+
+    registry.push_str("/// Every scaffolded puzzle, compiled into this binary.\n");
+    registry.push_str("pub static SOLUTIONS: &[advent_of_code::template::runner::Solution] = &[\n");
+
+    for (year, day) in &puzzles {
+        registry.push_str(&format!(
+            "    advent_of_code::template::runner::Solution {{ puzzle: advent_of_code::template::PuzzleId::new(advent_of_code::year!({year}), advent_of_code::day!({day})), run: day_{year}_{day:02}::run_solution }},\n"
+        ));
+    }
+
+    registry.push_str("];\n");
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("registry.rs"), registry).unwrap();
+}
@@ -97,13 +97,14 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result: Option<u64> = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result: Option<u64> =
+            part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(3));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(6));
     }
 
@@ -1,79 +1,265 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, examples, read, run_day, scaffold, solve, time,
+};
+use advent_of_code::template::run_multi::RunMultiOptions;
 use args::{AppArguments, parse};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
+use advent_of_code::template::PuzzleId;
 #[cfg(feature = "today")]
 use std::process;
 
+/// Every scaffolded day compiled into this binary, generated by `build.rs`.
+/// Excluded from test builds (the days' tests already run per binary) and when profiling with dhat,
+/// which needs one global allocator per day. `all` and `time` then fall back to running each day via cargo.
+#[cfg(not(any(test, feature = "dhat-heap")))]
+mod registry {
+    include!(concat!(env!("OUT_DIR"), "/registry.rs"));
+}
+
+#[cfg(any(test, feature = "dhat-heap"))]
+mod registry {
+    pub static SOLUTIONS: &[advent_of_code::template::runner::Solution] = &[];
+}
+
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{
+        Day, PuzzleId, Year,
+        bench::{BenchConfig, ExplicitSettings, parse_duration, parse_samples},
+        commands::time::TimeOptions,
+        compare::parse_threshold,
+        runner::{INPUT_CONFLICT, InputSource, RunOptions},
+    };
+    use std::{env, process, time::Duration};
+
+    /// Environment variable holding the default for `--timeout`, e.g. `30s`.
+    const TIMEOUT_ENV: &str = "AOC_TIMEOUT";
+
+    /// Slowdown in percent that `time --compare` tolerates before reporting a regression.
+    const DEFAULT_COMPARE_THRESHOLD: f64 = 10.0;
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzle: PuzzleId,
         },
         Read {
-            day: Day,
+            puzzle: PuzzleId,
+        },
+        Examples {
+            puzzle: PuzzleId,
+            overwrite: bool,
         },
         Scaffold {
-            day: Day,
+            puzzle: PuzzleId,
             download: bool,
             overwrite: bool,
         },
         Solve {
-            day: Day,
+            puzzle: PuzzleId,
             release: bool,
             dhat: bool,
-            submit: Option<u8>,
+            memory: bool,
+            options: RunOptions,
+            examples: bool,
         },
         All {
+            year: Year,
             release: bool,
+            jobs: usize,
+            timeout: Option<Duration>,
         },
         Time {
-            all: bool,
+            year: Year,
             day: Option<Day>,
-            store: bool,
+            options: TimeOptions,
+            bench: BenchConfig,
+            jobs: usize,
+            timeout: Option<Duration>,
+        },
+        TimeHistory {
+            puzzle: PuzzleId,
+        },
+        RunDay {
+            puzzle: PuzzleId,
+            options: RunOptions,
         },
         #[cfg(feature = "today")]
         Today,
     }
 
+    fn parse_bench_config(
+        args: &mut pico_args::Arguments,
+    ) -> Result<BenchConfig, Box<dyn std::error::Error>> {
+        let defaults = BenchConfig::default();
+
+        let warmup = args.opt_value_from_str("--warmup")?;
+        let outliers = args.opt_value_from_str("--outliers")?;
+        let budget = args.opt_value_from_fn("--budget", parse_duration)?;
+        let min_samples = args.opt_value_from_fn("--min-samples", parse_samples)?;
+        let max_samples = args.opt_value_from_fn("--max-samples", parse_samples)?;
+
+        let config = BenchConfig {
+            warmup: warmup.unwrap_or(defaults.warmup),
+            outliers: outliers.unwrap_or(defaults.outliers),
+            counters: args.contains("--counters"),
+            budget: budget.unwrap_or(defaults.budget),
+            min_samples: min_samples.unwrap_or(defaults.min_samples),
+            max_samples: max_samples.unwrap_or(defaults.max_samples),
+            explicit: ExplicitSettings {
+                warmup: warmup.is_some(),
+                outliers: outliers.is_some(),
+                budget: budget.is_some(),
+                min_samples: min_samples.is_some(),
+                max_samples: max_samples.is_some(),
+            },
+        };
+
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Reads `--timeout`, falling back to the [`TIMEOUT_ENV`] environment variable.
+    fn parse_timeout(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<Duration>, Box<dyn std::error::Error>> {
+        if let Some(timeout) = args.opt_value_from_fn("--timeout", parse_duration)? {
+            return Ok(Some(timeout));
+        }
+
+        match env::var(TIMEOUT_ENV) {
+            Ok(timeout) => Ok(Some(parse_duration(&timeout)?)),
+            Err(_) => Ok(None),
+        }
+    }
+
+    /// Reads `--year`, falling back to the year of the [`YEAR_ENV`](advent_of_code::template::YEAR_ENV) environment
+    /// variable.
+    fn parse_year(args: &mut pico_args::Arguments) -> Result<Year, Box<dyn std::error::Error>> {
+        match args.opt_value_from_str("--year")? {
+            Some(year) => Ok(year),
+            None => Ok(Year::from_env()?),
+        }
+    }
+
+    /// Reads the day and the year of a puzzle, see [`parse_year`].
+    fn parse_puzzle(
+        args: &mut pico_args::Arguments,
+    ) -> Result<PuzzleId, Box<dyn std::error::Error>> {
+        let year = parse_year(args)?;
+        Ok(PuzzleId::new(year, args.free_from_str()?))
+    }
+
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
+                year: parse_year(&mut args)?,
                 release: args.contains("--release"),
+                jobs: args.opt_value_from_str("--jobs")?.unwrap_or(1),
+                timeout: parse_timeout(&mut args)?,
+            },
+            Some("time") if args.contains("--history") => AppArguments::TimeHistory {
+                puzzle: parse_puzzle(&mut args)?,
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let report = args.contains("--report");
+                let bench = parse_bench_config(&mut args)?;
+                let jobs = args.opt_value_from_str("--jobs")?.unwrap_or(1);
+                let allow_parallel = args.contains("--allow-parallel");
+                let timeout = parse_timeout(&mut args)?;
+                let year = parse_year(&mut args)?;
+                let compare = if args.contains("--compare") {
+                    Some(
+                        args.opt_value_from_fn("--threshold", parse_threshold)?
+                            .unwrap_or(DEFAULT_COMPARE_THRESHOLD),
+                    )
+                } else {
+                    None
+                };
 
                 AppArguments::Time {
-                    all,
+                    year,
                     day: args.opt_free_from_str()?,
-                    store,
+                    options: TimeOptions {
+                        run_all: all,
+                        store,
+                        allow_parallel,
+                        compare_threshold: compare,
+                        report,
+                    },
+                    bench,
+                    jobs,
+                    timeout,
+                }
+            }
+            // internal: runs a single day in a child process of `run_multi`.
+            Some("run-day") => {
+                let time = if args.contains("--time") {
+                    Some(parse_bench_config(&mut args)?)
+                } else {
+                    None
+                };
+
+                AppArguments::RunDay {
+                    puzzle: args.free_from_str()?,
+                    options: RunOptions {
+                        time,
+                        submit: None,
+                        input: InputSource::Puzzle,
+                    },
                 }
             }
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(&mut args)?,
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(&mut args)?,
+            },
+            Some("examples") => AppArguments::Examples {
+                puzzle: parse_puzzle(&mut args)?,
+                overwrite: args.contains("--overwrite"),
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(&mut args)?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
-            },
+            Some("solve") => {
+                let time = if args.contains("--time") {
+                    Some(parse_bench_config(&mut args)?)
+                } else {
+                    None
+                };
+
+                let input = match (
+                    args.opt_value_from_str::<_, String>("--input")?,
+                    args.contains("--example"),
+                ) {
+                    (Some(_), true) => return Err(INPUT_CONFLICT.into()),
+                    (Some(arg), false) => InputSource::from_arg(&arg),
+                    (None, true) => InputSource::Example,
+                    (None, false) => InputSource::Puzzle,
+                };
+
+                let options = RunOptions {
+                    time,
+                    submit: args.opt_value_from_str("--submit")?,
+                    input,
+                };
+                options.validate()?;
+
+                AppArguments::Solve {
+                    puzzle: parse_puzzle(&mut args)?,
+                    release: args.contains("--release"),
+                    dhat: args.contains("--dhat"),
+                    memory: args.contains("--memory"),
+                    options,
+                    examples: args.contains("--examples"),
+                }
+            }
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
             Some(x) => {
@@ -102,33 +288,61 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
+            AppArguments::All {
+                year,
+                release,
+                jobs,
+                timeout,
+            } => all::handle(year, release, jobs, timeout, registry::SOLUTIONS),
+            AppArguments::Time {
+                year,
                 day,
+                options,
+                bench,
+                jobs,
+                timeout,
+            } => {
+                let run_options = RunMultiOptions {
+                    is_release: true,
+                    is_timed: true,
+                    bench,
+                    jobs,
+                    timeout,
+                };
+                time::handle(year, day, &options, &run_options, registry::SOLUTIONS);
+            }
+            AppArguments::TimeHistory { puzzle } => time::handle_history(puzzle),
+            AppArguments::RunDay { puzzle, options } => {
+                run_day::handle(puzzle, &options, registry::SOLUTIONS);
+            }
+            AppArguments::Download { puzzle } => download::handle(puzzle),
+            AppArguments::Read { puzzle } => read::handle(puzzle),
+            AppArguments::Examples { puzzle, overwrite } => examples::handle(puzzle, overwrite),
+            AppArguments::Scaffold {
+                puzzle,
                 download,
                 overwrite,
             } => {
-                scaffold::handle(day, overwrite);
+                scaffold::handle(puzzle, overwrite);
                 if download {
-                    download::handle(day);
+                    download::handle(puzzle);
                 }
             }
             AppArguments::Solve {
-                day,
+                puzzle,
                 release,
                 dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
+                memory,
+                options,
+                examples,
+            } => solve::handle(puzzle, release, dhat, memory, &options, examples),
             #[cfg(feature = "today")]
             AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+                match PuzzleId::today() {
+                    Some(puzzle) => {
+                        scaffold::handle(puzzle, false);
+                        download::handle(puzzle);
+                        read::handle(puzzle)
                     }
                     None => {
                         eprintln!(
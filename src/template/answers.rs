@@ -0,0 +1,272 @@
+/// Store of known answers, used to verify every run of a solution so that a refactor that breaks an answer is noticed.
+///
+/// Every year has its own store, e.g. `data/2024/answers.json`. It is filled by `cargo solve <day> --submit <part>`
+/// when a submission is accepted, and can be edited by hand:
+///
+/// ```json
+/// {
+///   "01": { "part_1": "142", "part_2": "281" },
+///   "02": { "part_1": "8" }
+/// }
+/// ```
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::{fs, io};
+
+use tinyjson::JsonValue;
+
+use crate::template::{ANSI_GREEN, ANSI_RED, ANSI_RESET, Day, PuzzleId, Year, data_dir};
+
+static ANSWERS_FILE_NAME: &str = "answers.json";
+
+/// Known answers of all days of a year, indexed by day and part.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    data: BTreeMap<Day, [Option<String>; 2]>,
+}
+
+/// Result of checking an answer against the store.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Match,
+    Mismatch {
+        expected: String,
+    },
+    /// No answer is stored for the part yet.
+    Unknown,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Match => write!(f, "{ANSI_GREEN}✔{ANSI_RESET}"),
+            // multi-line answers such as grids do not fit on the result line.
+            Verdict::Mismatch { expected } if expected.contains('\n') => {
+                write!(f, "{ANSI_RED}✘ expected a different answer{ANSI_RESET}")
+            }
+            Verdict::Mismatch { expected } => {
+                write!(f, "{ANSI_RED}✘ expected {expected}{ANSI_RESET}")
+            }
+            Verdict::Unknown => f.write_str("?"),
+        }
+    }
+}
+
+impl Answers {
+    /// The store file of a year, e.g. `data/2024/answers.json`.
+    #[must_use]
+    pub fn path(year: Year) -> PathBuf {
+        data_dir(year).join(ANSWERS_FILE_NAME)
+    }
+
+    /// Reads the store of a year from its file. If not present, returns an empty store.
+    pub fn read_from_file(year: Year) -> Result<Self, String> {
+        match fs::read_to_string(Self::path(year)) {
+            Ok(content) => content.parse(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    pub fn store_file(&self, year: Year) -> Result<(), io::Error> {
+        let json = JsonValue::from(self);
+        fs::create_dir_all(data_dir(year))?;
+        let mut file = fs::File::create(Self::path(year))?;
+        json.format_to(&mut file)
+    }
+
+    #[must_use]
+    pub fn get(&self, day: Day, part: u8) -> Option<&str> {
+        self.data
+            .get(&day)
+            .and_then(|answers| answers.get(usize::from(part).checked_sub(1)?))
+            .and_then(Option::as_deref)
+    }
+
+    /// Stores the answer of a part, replacing any previous one.
+    ///
+    /// # Panics
+    /// Panics if `part` is not `1` or `2`.
+    pub fn set(&mut self, day: Day, part: u8, answer: &str) {
+        assert!(matches!(part, 1 | 2), "expected part to be 1 or 2");
+        self.data.entry(day).or_default()[usize::from(part - 1)] = Some(answer.into());
+    }
+
+    #[must_use]
+    pub fn check(&self, day: Day, part: u8, answer: &str) -> Verdict {
+        match self.get(day, part) {
+            Some(expected) if expected == answer => Verdict::Match,
+            Some(expected) => Verdict::Mismatch {
+                expected: expected.into(),
+            },
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+/// Checks an answer against the store file of its year. An unreadable store is reported and treated as empty.
+pub fn check(puzzle: PuzzleId, part: u8, answer: &str) -> Verdict {
+    match Answers::read_from_file(puzzle.year) {
+        Ok(answers) => answers.check(puzzle.day, part, answer),
+        Err(e) => {
+            eprintln!(
+                "Failed to read {}: {e}",
+                Answers::path(puzzle.year).display()
+            );
+            Verdict::Unknown
+        }
+    }
+}
+
+/// Records an accepted answer in the store file of its year.
+pub fn record(puzzle: PuzzleId, part: u8, answer: &str) {
+    let path = Answers::path(puzzle.year);
+    let result = Answers::read_from_file(puzzle.year).and_then(|mut answers| {
+        answers.set(puzzle.day, part, answer);
+        answers.store_file(puzzle.year).map_err(|e| e.to_string())
+    });
+
+    match result {
+        Ok(()) => println!(
+            "Stored the answer of day {puzzle} part {part} in {}.",
+            path.display()
+        ),
+        Err(e) => eprintln!("Failed to store the answer in {}: {e}", path.display()),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answers> for JsonValue {
+    fn from(value: &Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        for (day, answers) in &value.data {
+            let mut parts: HashMap<String, JsonValue> = HashMap::new();
+            for (index, answer) in answers.iter().enumerate() {
+                if let Some(answer) = answer {
+                    parts.insert(
+                        format!("part_{}", index + 1),
+                        JsonValue::String(answer.clone()),
+                    );
+                }
+            }
+            map.insert(day.to_string(), JsonValue::Object(parts));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl std::str::FromStr for Answers {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = s
+            .parse::<JsonValue>()
+            .or(Err("answers file is not valid JSON."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answers to be a JSON object.")?;
+
+        let mut data = BTreeMap::new();
+
+        for (key, value) in json {
+            let day = key
+                .parse::<Day>()
+                .map_err(|_| format!("Expected \"{key}\" to be a day, e.g. \"01\"."))?;
+
+            let answers = parse_parts(key, value)?;
+            data.insert(day, answers);
+        }
+
+        Ok(Answers { data })
+    }
+}
+
+/// Parses the answers of both parts from an object such as `{ "part_1": "142", "part_2": "281" }`.
+pub(crate) fn parse_parts(key: &str, value: &JsonValue) -> Result<[Option<String>; 2], String> {
+    let parts = value
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or(format!("Expected answers of {key} to be an object."))?;
+
+    let mut answers: [Option<String>; 2] = [None, None];
+
+    for (index, answer) in answers.iter_mut().enumerate() {
+        let part_key = format!("part_{}", index + 1);
+        *answer = match parts.get(&part_key) {
+            None | Some(JsonValue::Null) => None,
+            Some(JsonValue::String(x)) => Some(x.clone()),
+            // JSON numbers are read as `f64`, which cannot represent every answer above 2^53.
+            Some(JsonValue::Number(_)) => {
+                return Err(format!(
+                    "Expected {key}.{part_key} to be a string, quote numeric answers to keep them exact."
+                ));
+            }
+            Some(_) => {
+                return Err(format!("Expected {key}.{part_key} to be a string."));
+            }
+        };
+    }
+
+    Ok(answers)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answers, Verdict};
+    use crate::day;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn checks_answers() {
+        let mut answers = Answers::default();
+        answers.set(day!(1), 1, "142");
+
+        assert_eq!(answers.check(day!(1), 1, "142"), Verdict::Match);
+        assert_eq!(
+            answers.check(day!(1), 1, "143"),
+            Verdict::Mismatch {
+                expected: "142".into()
+            }
+        );
+        assert_eq!(answers.check(day!(1), 2, "281"), Verdict::Unknown);
+        assert_eq!(answers.check(day!(2), 1, "142"), Verdict::Unknown);
+    }
+
+    #[test]
+    fn round_trips_json() {
+        let mut answers = Answers::default();
+        answers.set(day!(1), 1, "142");
+        answers.set(day!(1), 2, "281");
+        answers.set(day!(12), 2, "#..#\n#..#");
+
+        let json = JsonValue::from(&answers).stringify().unwrap();
+        assert_eq!(json.parse::<Answers>().unwrap(), answers);
+    }
+
+    #[test]
+    fn parses_hand_edited_json() {
+        let answers: Answers = r#"{ "03": { "part_1": "4361", "part_2": "467835" } }"#
+            .parse()
+            .unwrap();
+        assert_eq!(answers.get(day!(3), 1), Some("4361"));
+        assert_eq!(answers.get(day!(3), 2), Some("467835"));
+
+        // numbers would lose precision, e.g. 2^53 + 1.
+        assert_eq!(
+            r#"{ "03": { "part_1": 9007199254740993 } }"#.parse::<Answers>(),
+            Err(
+                "Expected 03.part_1 to be a string, quote numeric answers to keep them exact."
+                    .into()
+            )
+        );
+
+        assert!(r#"{ "3x": {} }"#.parse::<Answers>().is_err());
+        assert!(r#"{ "03": { "part_1": true } }"#.parse::<Answers>().is_err());
+    }
+}
@@ -4,7 +4,8 @@ use std::{
     process::{Command, Output, Stdio},
 };
 
-use crate::template::Day;
+use crate::template::PuzzleId;
+use crate::template::backend::{Backend, BackendError, SubmissionOutcome, input_path, puzzle_path};
 
 #[derive(Debug)]
 pub enum AocCommandError {
@@ -25,6 +26,32 @@ impl Display for AocCommandError {
     }
 }
 
+/// Backend that calls the external aoc-cli binary.
+pub struct AocCli;
+
+impl Backend for AocCli {
+    fn name(&self) -> &'static str {
+        "aoc-cli"
+    }
+
+    fn download(&self, puzzle: PuzzleId) -> Result<(), BackendError> {
+        download(puzzle).map(|_| ()).map_err(BackendError::AocCli)
+    }
+
+    fn read(&self, puzzle: PuzzleId) -> Result<(), BackendError> {
+        read(puzzle).map(|_| ()).map_err(BackendError::AocCli)
+    }
+
+    fn submit(
+        &self,
+        puzzle: PuzzleId,
+        part: u8,
+        answer: &str,
+    ) -> Result<SubmissionOutcome, BackendError> {
+        submit(puzzle, part, answer).map_err(BackendError::AocCli)
+    }
+}
+
 pub fn check() -> Result<(), AocCommandError> {
     Command::new("aoc")
         .arg("-V")
@@ -33,8 +60,8 @@ pub fn check() -> Result<(), AocCommandError> {
     Ok(())
 }
 
-pub fn read(day: Day) -> Result<Output, AocCommandError> {
-    let puzzle_path = get_puzzle_path(day);
+pub fn read(puzzle: PuzzleId) -> Result<Output, AocCommandError> {
+    let puzzle_path = puzzle_path(puzzle);
 
     let args = build_args(
         "read",
@@ -43,15 +70,15 @@ pub fn read(day: Day) -> Result<Output, AocCommandError> {
             "--puzzle-file".into(),
             puzzle_path,
         ],
-        day,
+        puzzle,
     );
 
     call_aoc_cli(&args)
 }
 
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
-    let input_path = get_input_path(day);
-    let puzzle_path = get_puzzle_path(day);
+pub fn download(puzzle: PuzzleId) -> Result<Output, AocCommandError> {
+    let input_path = input_path(puzzle);
+    let puzzle_path = puzzle_path(puzzle);
 
     let args = build_args(
         "download",
@@ -62,7 +89,7 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
             "--puzzle-file".into(),
             puzzle_path.to_string(),
         ],
-        day,
+        puzzle,
     );
 
     let output = call_aoc_cli(&args)?;
@@ -72,38 +99,43 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
     Ok(output)
 }
 
-pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
+/// Submits an answer and classifies the response. The output of aoc-cli is captured rather than printed.
+pub fn submit(
+    puzzle: PuzzleId,
+    part: u8,
+    result: &str,
+) -> Result<SubmissionOutcome, AocCommandError> {
     // workaround: the argument order is inverted for submit.
-    let mut args = build_args("submit", &[], day);
+    let mut args = build_args("submit", &[], puzzle);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
-}
 
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
-}
+    let output = Command::new("aoc")
+        .args(&args)
+        .output()
+        .map_err(|_| AocCommandError::CommandNotCallable)?;
 
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
-}
+    let outcome = SubmissionOutcome::from_output(&String::from_utf8_lossy(&output.stdout));
 
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
+    match outcome {
+        SubmissionOutcome::Unrecognized(_) if !output.status.success() => {
+            eprint!("{}", String::from_utf8_lossy(&output.stderr));
+            Err(AocCommandError::BadExitStatus(output))
+        }
+        outcome => Ok(outcome),
     }
 }
 
-fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
+fn build_args(command: &str, args: &[String], puzzle: PuzzleId) -> Vec<String> {
     let mut cmd_args = args.to_vec();
 
-    if let Some(year) = get_year() {
-        cmd_args.push("--year".into());
-        cmd_args.push(year.to_string());
-    }
-
-    cmd_args.append(&mut vec!["--day".into(), day.to_string(), command.into()]);
+    cmd_args.append(&mut vec![
+        "--year".into(),
+        puzzle.year.to_string(),
+        "--day".into(),
+        puzzle.day.to_string(),
+        command.into(),
+    ]);
 
     cmd_args
 }
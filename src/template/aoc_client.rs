@@ -0,0 +1,476 @@
+/// Built-in Advent of Code client, a backend that does not depend on the external aoc-cli binary.
+/// Enabled with the `native-client` feature.
+///
+/// The session cookie is read from the `AOC_SESSION` environment variable or, like aoc-cli does, from the file
+/// `~/.adventofcode.session`. `AOC_BASE_URL` overrides the address of the website, e.g. to test against a local server.
+use std::{env, fmt::Display, fs, io, path::PathBuf, time::Duration};
+
+use ureq::{Agent, Body, http::Response};
+
+use crate::template::PuzzleId;
+use crate::template::backend::{Backend, BackendError, SubmissionOutcome, input_path, puzzle_path};
+
+pub static SESSION_ENV: &str = "AOC_SESSION";
+pub static BASE_URL_ENV: &str = "AOC_BASE_URL";
+
+static DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+static SESSION_FILE_NAME: &str = ".adventofcode.session";
+static USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust (native-client)";
+
+#[derive(Debug)]
+pub enum ClientError {
+    MissingSession,
+    Http(ureq::Error),
+    /// The website responded with an unexpected status code.
+    Status {
+        url: String,
+        status: u16,
+    },
+    IO(io::Error),
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set {SESSION_ENV} or store it in ~/{SESSION_FILE_NAME}."
+            ),
+            ClientError::Http(e) => write!(f, "request failed: {e}"),
+            ClientError::Status { url, status } => {
+                write!(f, "request to {url} failed with status {status}.")
+            }
+            ClientError::IO(e) => write!(f, "failed to write file: {e}"),
+        }
+    }
+}
+
+impl From<ClientError> for BackendError {
+    fn from(e: ClientError) -> Self {
+        BackendError::Client(e)
+    }
+}
+
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    agent: Agent,
+}
+
+impl AocClient {
+    #[must_use]
+    pub fn new(base_url: &str, session: &str) -> Self {
+        let agent = Agent::config_builder()
+            .http_status_as_error(false)
+            .timeout_global(Some(Duration::from_secs(30)))
+            .build()
+            .new_agent();
+
+        Self {
+            base_url: base_url.trim_end_matches('/').into(),
+            session: session.trim().into(),
+            agent,
+        }
+    }
+
+    /// Creates a client from the environment, see the module documentation.
+    pub fn from_env() -> Result<Self, ClientError> {
+        let session = env::var(SESSION_ENV)
+            .ok()
+            .or_else(|| fs::read_to_string(session_file()?).ok())
+            .filter(|x| !x.trim().is_empty())
+            .ok_or(ClientError::MissingSession)?;
+
+        let base_url = env::var(BASE_URL_ENV).unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+
+        Ok(Self::new(&base_url, &session))
+    }
+
+    pub fn fetch_input(&self, puzzle: PuzzleId) -> Result<String, ClientError> {
+        self.get(&format!("{}/input", puzzle_url_path(puzzle)))
+    }
+
+    /// Fetches the puzzle page and returns its description as markdown.
+    pub fn fetch_puzzle(&self, puzzle: PuzzleId) -> Result<String, ClientError> {
+        self.get(&puzzle_url_path(puzzle))
+            .map(|html| puzzle_markdown(&html))
+    }
+
+    pub fn submit_answer(
+        &self,
+        puzzle: PuzzleId,
+        part: u8,
+        answer: &str,
+    ) -> Result<SubmissionOutcome, ClientError> {
+        let url = format!("{}{}/answer", self.base_url, puzzle_url_path(puzzle));
+
+        let response = self
+            .agent
+            .post(&url)
+            .header("Cookie", &self.cookie())
+            .header("User-Agent", USER_AGENT)
+            .send_form([("level", part.to_string().as_str()), ("answer", answer)])
+            .map_err(ClientError::Http)?;
+
+        let html = read_body(response, &url)?;
+        Ok(SubmissionOutcome::from_output(&article_text(&html)))
+    }
+
+    fn get(&self, path: &str) -> Result<String, ClientError> {
+        let url = format!("{}{path}", self.base_url);
+
+        let response = self
+            .agent
+            .get(&url)
+            .header("Cookie", &self.cookie())
+            .header("User-Agent", USER_AGENT)
+            .call()
+            .map_err(ClientError::Http)?;
+
+        read_body(response, &url)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+}
+
+impl Backend for AocClient {
+    fn name(&self) -> &'static str {
+        "the native client"
+    }
+
+    fn download(&self, puzzle: PuzzleId) -> Result<(), BackendError> {
+        let input = self.fetch_input(puzzle)?;
+        let description = self.fetch_puzzle(puzzle)?;
+
+        let input_path = input_path(puzzle);
+        let puzzle_path = puzzle_path(puzzle);
+        fs::write(&input_path, input).map_err(ClientError::IO)?;
+        fs::write(&puzzle_path, description).map_err(ClientError::IO)?;
+
+        println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+        println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+        Ok(())
+    }
+
+    fn read(&self, puzzle: PuzzleId) -> Result<(), BackendError> {
+        let description = self.fetch_puzzle(puzzle)?;
+        fs::write(puzzle_path(puzzle), &description).map_err(ClientError::IO)?;
+        println!("{description}");
+        Ok(())
+    }
+
+    fn submit(
+        &self,
+        puzzle: PuzzleId,
+        part: u8,
+        answer: &str,
+    ) -> Result<SubmissionOutcome, BackendError> {
+        Ok(self.submit_answer(puzzle, part, answer)?)
+    }
+}
+
+/// The path of a puzzle on the website, e.g. `/2024/day/1`.
+fn puzzle_url_path(puzzle: PuzzleId) -> String {
+    format!("/{}/day/{}", puzzle.year, puzzle.day.into_inner())
+}
+
+fn session_file() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(|home| PathBuf::from(home).join(SESSION_FILE_NAME))
+}
+
+fn read_body(mut response: Response<Body>, url: &str) -> Result<String, ClientError> {
+    let status = response.status();
+    let body = response
+        .body_mut()
+        .read_to_string()
+        .map_err(ClientError::Http)?;
+
+    if status.is_success() {
+        Ok(body)
+    } else {
+        Err(ClientError::Status {
+            url: url.into(),
+            status: status.as_u16(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Converts the description of a puzzle page, i.e. all `<article class="day-desc">` elements, to markdown.
+fn puzzle_markdown(html: &str) -> String {
+    const ARTICLE: &str = "<article class=\"day-desc\">";
+
+    let mut markdown = String::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find(ARTICLE) {
+        let body = &rest[start + ARTICLE.len()..];
+        let end = body.find("</article>").unwrap_or(body.len());
+        markdown.push_str(&html_to_markdown(&body[..end]));
+        markdown.push_str("\n\n");
+        rest = &body[end..];
+    }
+
+    tidy_markdown(&markdown)
+}
+
+/// Returns the plain text of the first `<article>` element, which holds the response to a submission.
+fn article_text(html: &str) -> String {
+    let Some(start) = html.find("<article") else {
+        return html.into();
+    };
+    let body = &html[start..];
+    let end = body.find("</article>").unwrap_or(body.len());
+
+    let mut text = String::new();
+    let mut rest = &body[..end];
+
+    while let Some(tag_start) = rest.find('<') {
+        text.push_str(&decode_entities(&rest[..tag_start]));
+        rest = rest[tag_start..]
+            .find('>')
+            .map_or("", |tag_end| &rest[tag_start + tag_end + 1..]);
+    }
+    text.push_str(&decode_entities(rest));
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Converts the subset of HTML used in puzzle descriptions to markdown.
+fn html_to_markdown(html: &str) -> String {
+    let mut markdown = String::new();
+    let mut in_pre = false;
+    let mut href: Option<String> = None;
+    let mut rest = html;
+
+    loop {
+        let tag_start = rest.find('<').unwrap_or(rest.len());
+        let text = decode_entities(&rest[..tag_start]);
+
+        if in_pre {
+            markdown.push_str(&text);
+        } else {
+            // line breaks in the source are insignificant outside of code blocks.
+            markdown.push_str(&text.replace('\n', " "));
+        }
+
+        let Some(tag_len) = rest[tag_start..].find('>') else {
+            break;
+        };
+        let tag = &rest[tag_start + 1..tag_start + tag_len];
+        rest = &rest[tag_start + tag_len + 1..];
+
+        let (is_closing, tag) = match tag.strip_prefix('/') {
+            Some(tag) => (true, tag),
+            None => (false, tag),
+        };
+        let name = tag.split_whitespace().next().unwrap_or_default();
+
+        match (name, is_closing) {
+            ("h2", false) => markdown.push_str("\n## "),
+            ("h2" | "p" | "ul", true) => markdown.push_str("\n\n"),
+            ("pre", false) => {
+                in_pre = true;
+                markdown.push_str("\n```\n");
+            }
+            ("pre", true) => {
+                in_pre = false;
+                if !markdown.ends_with('\n') {
+                    markdown.push('\n');
+                }
+                markdown.push_str("```\n\n");
+            }
+            ("code", _) if !in_pre => markdown.push('`'),
+            ("em", _) if !in_pre => markdown.push('*'),
+            ("li", false) => markdown.push_str("\n- "),
+            ("a", false) => {
+                href = attribute(tag, "href");
+                markdown.push('[');
+            }
+            ("a", true) => match href.take() {
+                Some(href) => markdown.push_str(&format!("]({href})")),
+                None => markdown.push(']'),
+            },
+            _ => {}
+        }
+    }
+
+    markdown
+}
+
+/// Trims the lines outside of code blocks and collapses consecutive blank lines.
+fn tidy_markdown(markdown: &str) -> String {
+    let mut lines: Vec<&str> = vec![];
+    let mut in_code = false;
+
+    for line in markdown.lines() {
+        let line = if in_code { line } else { line.trim() };
+
+        if line == "```" {
+            in_code = !in_code;
+        }
+
+        if line.is_empty() && !in_code && lines.last().is_none_or(|x| x.is_empty()) {
+            continue;
+        }
+
+        lines.push(line);
+    }
+
+    while lines.last().is_some_and(|x| x.is_empty()) {
+        lines.pop();
+    }
+
+    lines.join("\n") + "\n"
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let start = tag.find(&format!("{name}=\""))? + name.len() + 2;
+    let end = start + tag[start..].find('"')?;
+    Some(decode_entities(&tag[start..end]))
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{AocClient, ClientError, article_text, puzzle_markdown};
+    use crate::{
+        day,
+        template::{PuzzleId, backend::SubmissionOutcome},
+        year,
+    };
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+
+    /// Serves a single request with the given status and body, and sends the received request to the returned channel.
+    fn mock_server(status: u16, body: &'static str) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+
+            let mut request = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(x) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = x.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut content = vec![0; content_length];
+            reader.read_exact(&mut content).unwrap();
+            request.push_str(&String::from_utf8(content).unwrap());
+
+            let response = format!(
+                "HTTP/1.1 {status} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            tx.send(request).unwrap();
+        });
+
+        (base_url, rx)
+    }
+
+    #[test]
+    fn fetches_inputs() {
+        let (base_url, requests) = mock_server(200, "1721\n979\n");
+        let client = AocClient::new(&base_url, "secret\n");
+
+        assert_eq!(
+            client
+                .fetch_input(PuzzleId::new(year!(2020), day!(1)))
+                .unwrap(),
+            "1721\n979\n"
+        );
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("GET /2020/day/1/input HTTP/1.1"));
+        assert!(
+            request
+                .to_lowercase()
+                .contains("cookie: session=secret\r\n")
+        );
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (base_url, requests) = mock_server(
+            200,
+            "<main><article><p>That's not the right answer; your answer is too high. Please wait one minute.</p></article></main>",
+        );
+        let client = AocClient::new(&base_url, "secret");
+
+        let outcome = client
+            .submit_answer(PuzzleId::new(year!(2020), day!(12)), 2, "514579")
+            .unwrap();
+        assert_eq!(outcome, SubmissionOutcome::TooHigh);
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /2020/day/12/answer HTTP/1.1"));
+        assert!(request.ends_with("level=2&answer=514579"));
+    }
+
+    #[test]
+    fn reports_bad_status() {
+        let (base_url, _requests) = mock_server(400, "Please log in.");
+        let client = AocClient::new(&base_url, "expired");
+
+        assert!(matches!(
+            client.fetch_input(PuzzleId::new(year!(2020), day!(1))),
+            Err(ClientError::Status { status: 400, .. })
+        ));
+    }
+
+    #[test]
+    fn converts_puzzles_to_markdown() {
+        let html = r#"<main><article class="day-desc"><h2>--- Day 1: Report Repair ---</h2><p>Find the <em>two entries</em> that sum to <code>2020</code>.</p>
+<p>For example:</p>
+<pre><code>1721
+<em>979</em>
+</code></pre>
+<ul><li>See <a href="/2020/about">about</a></li></ul>
+</article><p>Answer: <input type="text"/></p></main>"#;
+
+        assert_eq!(
+            puzzle_markdown(html),
+            "## --- Day 1: Report Repair ---\n\nFind the *two entries* that sum to `2020`.\n\nFor example:\n\n```\n1721\n979\n```\n\n- See [about](/2020/about)\n"
+        );
+    }
+
+    #[test]
+    fn extracts_article_text() {
+        let html = "<html><article><p>You gave an answer too recently; <span>You have 38s left to wait.</span></p></article></html>";
+        assert_eq!(
+            article_text(html),
+            "You gave an answer too recently; You have 38s left to wait."
+        );
+    }
+}
@@ -0,0 +1,286 @@
+/// Interchangeable backends that talk to the Advent of Code website.
+///
+/// By default, the external aoc-cli binary is used, see [`AocCli`]. With the `native-client` feature, a built-in HTTP
+/// client is used instead, see [`AocClient`](crate::template::aoc_client::AocClient). Set `AOC_BACKEND` to `aoc-cli` or
+/// `native` to choose explicitly.
+use std::{env, fmt::Display, fs, io, time::Duration};
+
+use crate::template::aoc_cli::{self, AocCli, AocCommandError};
+#[cfg(feature = "native-client")]
+use crate::template::aoc_client::{AocClient, ClientError};
+use crate::template::{
+    ANSI_BOLD, ANSI_GREEN, ANSI_RED, ANSI_RESET, ANSI_YELLOW, PuzzleId, data_dir,
+};
+
+/// Environment variable that selects the backend, `aoc-cli` or `native`.
+pub static BACKEND_ENV: &str = "AOC_BACKEND";
+
+/// Operations every backend supports.
+pub trait Backend {
+    /// Name of the backend for console output, e.g. `aoc-cli`.
+    fn name(&self) -> &'static str;
+
+    /// Downloads the input and the puzzle description of a puzzle to the `inputs` and `puzzles` directories of its
+    /// year, see [`input_path`] and [`puzzle_path`].
+    fn download(&self, puzzle: PuzzleId) -> Result<(), BackendError>;
+
+    /// Prints the puzzle description of a puzzle and stores it in the `puzzles` directory of its year.
+    fn read(&self, puzzle: PuzzleId) -> Result<(), BackendError>;
+
+    /// Submits the answer of a part and classifies the response.
+    fn submit(
+        &self,
+        puzzle: PuzzleId,
+        part: u8,
+        answer: &str,
+    ) -> Result<SubmissionOutcome, BackendError>;
+}
+
+#[derive(Debug)]
+pub enum BackendError {
+    AocCli(AocCommandError),
+    #[cfg(feature = "native-client")]
+    Client(ClientError),
+    /// `AOC_BACKEND` names a backend that does not exist or is not compiled in.
+    UnknownBackend(String),
+}
+
+impl Display for BackendError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BackendError::AocCli(AocCommandError::CommandNotFound) => write!(
+                f,
+                "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+            ),
+            BackendError::AocCli(e) => write!(f, "failed to call aoc-cli: {e}"),
+            #[cfg(feature = "native-client")]
+            BackendError::Client(e) => write!(f, "{e}"),
+            BackendError::UnknownBackend(name) => write!(
+                f,
+                "unknown backend \"{name}\" in {BACKEND_ENV}, expected \"aoc-cli\" or \"native\" (requires the `native-client` feature)."
+            ),
+        }
+    }
+}
+
+/// Selects the backend, see the module documentation.
+pub fn select() -> Result<Box<dyn Backend>, BackendError> {
+    let default = if cfg!(feature = "native-client") {
+        "native"
+    } else {
+        "aoc-cli"
+    };
+
+    match env::var(BACKEND_ENV).as_deref().unwrap_or(default) {
+        "aoc-cli" => {
+            aoc_cli::check().map_err(BackendError::AocCli)?;
+            Ok(Box::new(AocCli))
+        }
+        #[cfg(feature = "native-client")]
+        "native" => Ok(Box::new(
+            AocClient::from_env().map_err(BackendError::Client)?,
+        )),
+        name => Err(BackendError::UnknownBackend(name.into())),
+    }
+}
+
+/// The input file of a puzzle, e.g. `data/2024/inputs/01.txt`.
+#[must_use]
+pub fn input_path(puzzle: PuzzleId) -> String {
+    let path = data_dir(puzzle.year).join("inputs");
+    path.join(format!("{}.txt", puzzle.day))
+        .display()
+        .to_string()
+}
+
+/// The description file of a puzzle, e.g. `data/2024/puzzles/01.md`.
+#[must_use]
+pub fn puzzle_path(puzzle: PuzzleId) -> String {
+    let path = data_dir(puzzle.year).join("puzzles");
+    path.join(format!("{}.md", puzzle.day))
+        .display()
+        .to_string()
+}
+
+/// Creates the `inputs` and `puzzles` directories of the year of a puzzle, which backends write to.
+pub fn create_data_dirs(puzzle: PuzzleId) -> Result<(), io::Error> {
+    fs::create_dir_all(data_dir(puzzle.year).join("inputs"))?;
+    fs::create_dir_all(data_dir(puzzle.year).join("puzzles"))
+}
+
+/// Response of Advent of Code to a submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SubmissionOutcome {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// The answer was not checked because the previous submission was too recent.
+    RateLimited {
+        /// Time left until the next submission is accepted, if it could be read from the response.
+        wait: Option<Duration>,
+    },
+    /// The part was already solved, or the first part has not been solved yet.
+    WrongLevel,
+    /// A response that could not be classified, containing the raw output of aoc-cli.
+    Unrecognized(String),
+}
+
+impl SubmissionOutcome {
+    /// Classifies the text of the response to a submission.
+    #[must_use]
+    pub fn from_output(output: &str) -> Self {
+        if output.contains("That's the right answer") {
+            SubmissionOutcome::Correct
+        } else if output.contains("your answer is too high") {
+            SubmissionOutcome::TooHigh
+        } else if output.contains("your answer is too low") {
+            SubmissionOutcome::TooLow
+        } else if output.contains("That's not the right answer") {
+            SubmissionOutcome::Wrong
+        } else if output.contains("You gave an answer too recently") {
+            SubmissionOutcome::RateLimited {
+                wait: parse_wait(output),
+            }
+        } else if output.contains("You don't seem to be solving the right level") {
+            SubmissionOutcome::WrongLevel
+        } else {
+            SubmissionOutcome::Unrecognized(output.trim().into())
+        }
+    }
+
+    /// Whether the outcome proves the submitted answer wrong.
+    #[must_use]
+    pub fn is_wrong(&self) -> bool {
+        matches!(
+            self,
+            SubmissionOutcome::Wrong | SubmissionOutcome::TooHigh | SubmissionOutcome::TooLow
+        )
+    }
+
+    /// Colored, human-readable description of the outcome for the console.
+    #[must_use]
+    pub fn feedback(&self) -> String {
+        match self {
+            SubmissionOutcome::Correct => {
+                format!("{ANSI_GREEN}{ANSI_BOLD}✔ That's the right answer!{ANSI_RESET}")
+            }
+            SubmissionOutcome::Wrong => format!("{ANSI_RED}{ANSI_BOLD}✘ Wrong answer.{ANSI_RESET}"),
+            SubmissionOutcome::TooHigh => {
+                format!("{ANSI_RED}{ANSI_BOLD}✘ Wrong answer, too high.{ANSI_RESET}")
+            }
+            SubmissionOutcome::TooLow => {
+                format!("{ANSI_RED}{ANSI_BOLD}✘ Wrong answer, too low.{ANSI_RESET}")
+            }
+            SubmissionOutcome::RateLimited { wait: Some(wait) } => format!(
+                "{ANSI_YELLOW}{ANSI_BOLD}⏳ Submitted too recently, wait {wait:?} before trying again.{ANSI_RESET}"
+            ),
+            SubmissionOutcome::RateLimited { wait: None } => format!(
+                "{ANSI_YELLOW}{ANSI_BOLD}⏳ Submitted too recently, wait before trying again.{ANSI_RESET}"
+            ),
+            SubmissionOutcome::WrongLevel => format!(
+                "{ANSI_YELLOW}{ANSI_BOLD}? This part is already solved or not unlocked yet.{ANSI_RESET}"
+            ),
+            SubmissionOutcome::Unrecognized(output) => {
+                format!("{ANSI_YELLOW}{ANSI_BOLD}? Unrecognized response:{ANSI_RESET}\n{output}")
+            }
+        }
+    }
+}
+
+/// Displays the outcome as a short label, e.g. `too high`.
+impl Display for SubmissionOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            SubmissionOutcome::Correct => "correct",
+            SubmissionOutcome::Wrong => "wrong",
+            SubmissionOutcome::TooHigh => "too high",
+            SubmissionOutcome::TooLow => "too low",
+            SubmissionOutcome::RateLimited { .. } => "rate-limited",
+            SubmissionOutcome::WrongLevel => "not on the right level",
+            SubmissionOutcome::Unrecognized(_) => "unrecognized",
+        })
+    }
+}
+
+/// Reads the wait time from a rate-limit response, e.g. `You have 1m 5s left to wait.`
+fn parse_wait(output: &str) -> Option<Duration> {
+    let start = output.find("You have ")? + "You have ".len();
+    let end = start + output[start..].find(" left to wait")?;
+
+    let mut seconds = 0;
+    for token in output[start..end].split_whitespace() {
+        let unit = token.chars().last()?;
+        let value: u64 = token[..token.len() - unit.len_utf8()].parse().ok()?;
+        seconds += match unit {
+            'h' => value * 3600,
+            'm' => value * 60,
+            's' => value,
+            _ => return None,
+        };
+    }
+
+    Some(Duration::from_secs(seconds))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{SubmissionOutcome, parse_wait};
+    use std::time::Duration;
+
+    #[test]
+    fn classifies_submit_output() {
+        let cases = [
+            (
+                "That's the right answer! You are one gold star closer.",
+                SubmissionOutcome::Correct,
+            ),
+            (
+                "That's not the right answer; your answer is too high.",
+                SubmissionOutcome::TooHigh,
+            ),
+            (
+                "That's not the right answer; your answer is too low.",
+                SubmissionOutcome::TooLow,
+            ),
+            (
+                "That's not the right answer. If you're stuck, ...",
+                SubmissionOutcome::Wrong,
+            ),
+            (
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 38s left to wait.",
+                SubmissionOutcome::RateLimited {
+                    wait: Some(Duration::from_secs(38)),
+                },
+            ),
+            (
+                "You don't seem to be solving the right level. Did you already complete it?",
+                SubmissionOutcome::WrongLevel,
+            ),
+            (
+                "  error: session cookie expired\n",
+                SubmissionOutcome::Unrecognized("error: session cookie expired".into()),
+            ),
+        ];
+
+        for (output, expected) in cases {
+            assert_eq!(SubmissionOutcome::from_output(output), expected, "{output}");
+        }
+    }
+
+    #[test]
+    fn parses_wait_times() {
+        assert_eq!(
+            parse_wait("You have 1m 5s left to wait."),
+            Some(Duration::from_secs(65))
+        );
+        assert_eq!(
+            parse_wait("You have 2h left to wait."),
+            Some(Duration::from_secs(7200))
+        );
+        assert_eq!(parse_wait("You have to wait."), None);
+        assert_eq!(parse_wait("You have soon left to wait."), None);
+    }
+}
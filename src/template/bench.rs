@@ -0,0 +1,668 @@
+/// Configuration of the benchmarking phase, shared by `cargo solve --time` and `cargo time`.
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::time::Duration;
+use std::{env, fs, process};
+
+use tinyjson::JsonValue;
+
+use crate::template::{Day, PuzzleId, data_dir};
+
+static OVERRIDES_FILE_NAME: &str = "bench.json";
+
+/// How long a solution part is executed before samples are collected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Warmup {
+    /// Execute the part a fixed number of times.
+    Iterations(u32),
+    /// Execute the part until the given wall time has passed (at least once).
+    Time(Duration),
+}
+
+impl Default for Warmup {
+    fn default() -> Self {
+        Self::Time(Duration::from_millis(100))
+    }
+}
+
+/// Parses either a number of iterations (`5`) or a wall time in milliseconds or seconds (`500ms`, `2s`).
+impl FromStr for Warmup {
+    type Err = BenchArgError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || {
+            BenchArgError(format!(
+                "invalid warmup \"{s}\", expecting e.g. `5`, `500ms` or `2s`"
+            ))
+        };
+
+        if s.ends_with('s') {
+            parse_duration(s).map(Self::Time).map_err(|_| err())
+        } else {
+            s.parse().map(Self::Iterations).map_err(|_| err())
+        }
+    }
+}
+
+/// Parses a wall time in nanoseconds, microseconds, milliseconds or seconds, e.g. `250us`, `500ms` or `2s`.
+pub fn parse_duration(s: &str) -> Result<Duration, BenchArgError> {
+    let err = || {
+        BenchArgError(format!(
+            "invalid duration \"{s}\", expecting e.g. `500ms` or `2s`"
+        ))
+    };
+
+    let (value, from): (&str, fn(u64) -> Duration) = if let Some(x) = s.strip_suffix("ns") {
+        (x, Duration::from_nanos)
+    } else if let Some(x) = s.strip_suffix("us") {
+        (x, Duration::from_micros)
+    } else if let Some(x) = s.strip_suffix("ms") {
+        (x, Duration::from_millis)
+    } else if let Some(x) = s.strip_suffix('s') {
+        (x, Duration::from_secs)
+    } else {
+        return Err(err());
+    };
+
+    value.parse().map(from).map_err(|_| err())
+}
+
+/// Formats a duration in the largest unit that represents it exactly, so that [`parse_duration`] reads it back as is.
+#[must_use]
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+
+    [(1_000_000_000, "s"), (1_000_000, "ms"), (1_000, "us")]
+        .into_iter()
+        .find(|(factor, _)| nanos.is_multiple_of(*factor))
+        .map_or_else(
+            || format!("{nanos}ns"),
+            |(factor, unit)| format!("{}{unit}", nanos / factor),
+        )
+}
+
+impl Display for Warmup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Iterations(x) => write!(f, "{x}"),
+            Self::Time(x) => f.write_str(&format_duration(*x)),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Strategy used to discard outlier samples before computing statistics.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutlierFilter {
+    /// Keep all samples.
+    None,
+    /// Discard samples outside of `[Q1 - 1.5 * IQR, Q3 + 1.5 * IQR]`.
+    #[default]
+    Tukey,
+    /// Discard samples with a modified z-score (based on the median absolute deviation) above 3.5.
+    Mad,
+}
+
+impl FromStr for OutlierFilter {
+    type Err = BenchArgError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(Self::None),
+            "tukey" => Ok(Self::Tukey),
+            "mad" => Ok(Self::Mad),
+            _ => Err(BenchArgError(format!(
+                "invalid outlier filter \"{s}\", expecting one of `tukey`, `mad` or `none`"
+            ))),
+        }
+    }
+}
+
+impl Display for OutlierFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::None => f.write_str("none"),
+            Self::Tukey => f.write_str("tukey"),
+            Self::Mad => f.write_str("mad"),
+        }
+    }
+}
+
+impl OutlierFilter {
+    /// Returns the samples that are not considered outliers by this filter.
+    #[must_use]
+    pub fn apply(self, samples: &[Duration]) -> Vec<Duration> {
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        #[allow(clippy::cast_precision_loss)]
+        let nanos: Vec<f64> = sorted.iter().map(|x| x.as_nanos() as f64).collect();
+
+        let (low, high) = match self {
+            Self::None => return sorted,
+            Self::Tukey => {
+                let q1 = quantile(&nanos, 0.25);
+                let q3 = quantile(&nanos, 0.75);
+                let iqr = q3 - q1;
+                (q1 - 1.5 * iqr, q3 + 1.5 * iqr)
+            }
+            Self::Mad => {
+                let median = quantile(&nanos, 0.5);
+                let mut deviations: Vec<f64> = nanos.iter().map(|x| (x - median).abs()).collect();
+                deviations.sort_unstable_by(f64::total_cmp);
+                // a modified z-score of `0.6745 * (x - median) / mad` above 3.5 marks an outlier.
+                let limit = 3.5 * quantile(&deviations, 0.5) / 0.6745;
+                (median - limit, median + limit)
+            }
+        };
+
+        // a spread of zero means most samples are identical, nothing sensible to reject then.
+        if high <= low {
+            return sorted;
+        }
+
+        sorted
+            .into_iter()
+            .zip(nanos)
+            .filter(|(_, x)| (low..=high).contains(x))
+            .map(|(sample, _)| sample)
+            .collect()
+    }
+}
+
+/// Linearly interpolated quantile of sorted values.
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let pos = (sorted.len() - 1) as f64 * q;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let (lower, upper) = (pos.floor() as usize, pos.ceil() as usize);
+
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (pos - pos.floor())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Settings applied when benching a solution part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BenchConfig {
+    pub warmup: Warmup,
+    pub outliers: OutlierFilter,
+    /// Whether hardware performance counters are read while sampling (`--counters`), see [`crate::template::perf`].
+    pub counters: bool,
+    /// Wall time that sampling aims for. The number of samples is derived from it and the warmup.
+    pub budget: Duration,
+    pub min_samples: u32,
+    pub max_samples: u32,
+    /// The settings that were passed on the command line, see [`BenchConfig::for_puzzle`].
+    pub explicit: ExplicitSettings,
+}
+
+/// Marks the settings of a [`BenchConfig`] that were passed as command-line flags instead of left at their defaults.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ExplicitSettings {
+    pub warmup: bool,
+    pub outliers: bool,
+    pub budget: bool,
+    pub min_samples: bool,
+    pub max_samples: bool,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            warmup: Warmup::default(),
+            outliers: OutlierFilter::default(),
+            counters: false,
+            budget: Duration::from_secs(1),
+            min_samples: 10,
+            max_samples: 10000,
+            explicit: ExplicitSettings::default(),
+        }
+    }
+}
+
+impl BenchConfig {
+    /// Reads the settings from the arguments of the current process, falling back to defaults.
+    pub fn from_env_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+        let mut config = Self {
+            counters: args.iter().any(|x| x == "--counters"),
+            ..Self::default()
+        };
+
+        let value_of = |flag: &str| {
+            args.iter()
+                .position(|x| x == flag)
+                .and_then(|i| args.get(i + 1))
+        };
+
+        let result = (|| -> Result<(), BenchArgError> {
+            if let Some(warmup) = value_of("--warmup") {
+                config.warmup = warmup.parse()?;
+                config.explicit.warmup = true;
+            }
+            if let Some(outliers) = value_of("--outliers") {
+                config.outliers = outliers.parse()?;
+                config.explicit.outliers = true;
+            }
+            if let Some(budget) = value_of("--budget") {
+                config.budget = parse_duration(budget)?;
+                config.explicit.budget = true;
+            }
+            if let Some(min_samples) = value_of("--min-samples") {
+                config.min_samples = parse_samples(min_samples)?;
+                config.explicit.min_samples = true;
+            }
+            if let Some(max_samples) = value_of("--max-samples") {
+                config.max_samples = parse_samples(max_samples)?;
+                config.explicit.max_samples = true;
+            }
+            config.validate()
+        })();
+
+        if let Err(e) = result {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+
+        config
+    }
+
+    /// Returns the command-line arguments that reproduce these settings in a solution binary. Only the settings that
+    /// were passed explicitly are mirrored, so that the solution can apply its overrides to the others.
+    pub fn to_args(&self) -> Vec<String> {
+        let explicit = self.explicit;
+
+        let mut args: Vec<String> = [
+            (explicit.warmup, "--warmup", self.warmup.to_string()),
+            (explicit.outliers, "--outliers", self.outliers.to_string()),
+            (explicit.budget, "--budget", format_duration(self.budget)),
+            (
+                explicit.min_samples,
+                "--min-samples",
+                self.min_samples.to_string(),
+            ),
+            (
+                explicit.max_samples,
+                "--max-samples",
+                self.max_samples.to_string(),
+            ),
+        ]
+        .into_iter()
+        .filter(|(is_explicit, _, _)| *is_explicit)
+        .flat_map(|(_, flag, value)| [flag.into(), value])
+        .collect();
+
+        if self.counters {
+            args.push("--counters".into());
+        }
+
+        args
+    }
+
+    /// Checks that the sample bounds are consistent.
+    pub fn validate(&self) -> Result<(), BenchArgError> {
+        if self.min_samples > self.max_samples {
+            return Err(BenchArgError(format!(
+                "--min-samples ({}) must not exceed --max-samples ({})",
+                self.min_samples, self.max_samples
+            )));
+        }
+        Ok(())
+    }
+
+    /// Applies the override of a puzzle from the `bench.json` of its year, e.g. `data/2024/bench.json`, if there is
+    /// one. Overrides replace the defaults, but not the settings that were passed explicitly on the command line.
+    #[must_use]
+    pub fn for_puzzle(self, puzzle: PuzzleId) -> Self {
+        let path = data_dir(puzzle.year).join(OVERRIDES_FILE_NAME);
+        let Ok(overrides) = fs::read_to_string(&path) else {
+            return self;
+        };
+
+        match self.with_override(&overrides, puzzle.day) {
+            Ok(config) => config,
+            Err(e) => {
+                eprintln!(
+                    "Ignoring invalid bench override for day {} in {}: {e}",
+                    puzzle.day,
+                    path.display()
+                );
+                self
+            }
+        }
+    }
+
+    /// Applies the override of `day` from the contents of an overrides file, e.g.:
+    ///
+    /// ```json
+    /// { "07": { "budget": "5s", "min_samples": 3, "max_samples": 20, "warmup": "1" } }
+    /// ```
+    fn with_override(self, overrides: &str, day: Day) -> Result<Self, BenchArgError> {
+        let err = |msg: &str| BenchArgError(msg.into());
+
+        let Some(entry) = override_entry(overrides, day)? else {
+            return Ok(self);
+        };
+
+        let string = |key: &str| string_value(&entry, key);
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let samples = |key: &str| -> Result<Option<u32>, BenchArgError> {
+            entry
+                .get(key)
+                .map(|v| {
+                    v.get::<f64>()
+                        .filter(|x| **x >= 1_f64)
+                        .map(|x| *x as u32)
+                        .ok_or_else(|| err(&format!("expected `{key}` to be a positive number.")))
+                })
+                .transpose()
+        };
+
+        let mut config = self;
+        let explicit = self.explicit;
+
+        if let Some(warmup) = string("warmup")?.filter(|_| !explicit.warmup) {
+            config.warmup = warmup.parse()?;
+        }
+        if let Some(outliers) = string("outliers")?.filter(|_| !explicit.outliers) {
+            config.outliers = outliers.parse()?;
+        }
+        if let Some(budget) = string("budget")?.filter(|_| !explicit.budget) {
+            config.budget = parse_duration(budget)?;
+        }
+        if let Some(min_samples) = samples("min_samples")?.filter(|_| !explicit.min_samples) {
+            config.min_samples = min_samples;
+        }
+        if let Some(max_samples) = samples("max_samples")?.filter(|_| !explicit.max_samples) {
+            config.max_samples = max_samples;
+        }
+
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+/// Reads the timeout of a puzzle from the `bench.json` of its year, e.g. `{ "07": { "timeout": "120s" } }`.
+/// It replaces the `--timeout` of `cargo all` and `cargo time` for days that legitimately run longer than the rest.
+pub fn timeout_override(puzzle: PuzzleId) -> Option<Duration> {
+    let path = data_dir(puzzle.year).join(OVERRIDES_FILE_NAME);
+    let overrides = fs::read_to_string(&path).ok()?;
+
+    parse_timeout_override(&overrides, puzzle.day).unwrap_or_else(|e| {
+        eprintln!(
+            "Ignoring invalid timeout override for day {} in {}: {e}",
+            puzzle.day,
+            path.display()
+        );
+        None
+    })
+}
+
+fn parse_timeout_override(overrides: &str, day: Day) -> Result<Option<Duration>, BenchArgError> {
+    let Some(entry) = override_entry(overrides, day)? else {
+        return Ok(None);
+    };
+
+    string_value(&entry, "timeout")?
+        .map(|timeout| parse_duration(timeout))
+        .transpose()
+}
+
+/// Returns the override object of `day` from the contents of an overrides file, if there is one.
+fn override_entry(
+    overrides: &str,
+    day: Day,
+) -> Result<Option<HashMap<String, JsonValue>>, BenchArgError> {
+    let err = |msg: &str| BenchArgError(msg.into());
+
+    let json = JsonValue::from_str(overrides).map_err(|_| err("not valid JSON."))?;
+
+    let Some(value) = json
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or_else(|| err("expected JSON document to be an object."))?
+        .get(&day.to_string())
+    else {
+        return Ok(None);
+    };
+
+    value
+        .get::<HashMap<String, JsonValue>>()
+        .cloned()
+        .map(Some)
+        .ok_or_else(|| err("expected override to be an object."))
+}
+
+fn string_value<'a>(
+    entry: &'a HashMap<String, JsonValue>,
+    key: &str,
+) -> Result<Option<&'a String>, BenchArgError> {
+    entry
+        .get(key)
+        .map(|v| {
+            v.get::<String>()
+                .ok_or_else(|| BenchArgError(format!("expected `{key}` to be a string.")))
+        })
+        .transpose()
+}
+
+/// Parses a positive number of samples.
+pub fn parse_samples(s: &str) -> Result<u32, BenchArgError> {
+    s.parse()
+        .ok()
+        .filter(|x| *x > 0)
+        .ok_or_else(|| BenchArgError(format!("invalid number of samples \"{s}\"")))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An error which can be returned when parsing benchmark settings.
+#[derive(Debug)]
+pub struct BenchArgError(String);
+
+impl Error for BenchArgError {}
+
+impl Display for BenchArgError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        BenchConfig, ExplicitSettings, OutlierFilter, Warmup, format_duration, parse_duration,
+        parse_samples, parse_timeout_override,
+    };
+    use crate::day;
+    use std::time::Duration;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_nanos).collect()
+    }
+
+    #[test]
+    fn parses_warmup() {
+        assert_eq!("5".parse::<Warmup>().unwrap(), Warmup::Iterations(5));
+        assert_eq!(
+            "250ms".parse::<Warmup>().unwrap(),
+            Warmup::Time(Duration::from_millis(250))
+        );
+        assert_eq!(
+            "2s".parse::<Warmup>().unwrap(),
+            Warmup::Time(Duration::from_secs(2))
+        );
+        assert!("fast".parse::<Warmup>().is_err());
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("500ms").unwrap(), Duration::from_millis(500));
+        assert_eq!(parse_duration("30s").unwrap(), Duration::from_secs(30));
+        assert_eq!(parse_duration("250us").unwrap(), Duration::from_micros(250));
+        assert_eq!(parse_duration("10ns").unwrap(), Duration::from_nanos(10));
+        assert!(parse_duration("30").is_err());
+        assert!(parse_duration("ms").is_err());
+    }
+
+    #[test]
+    fn formats_durations_exactly() {
+        for duration in [
+            Duration::from_secs(2),
+            Duration::from_millis(1500),
+            Duration::from_micros(2500),
+            Duration::from_nanos(1_000_000_001),
+            Duration::ZERO,
+        ] {
+            assert_eq!(
+                parse_duration(&format_duration(duration)).unwrap(),
+                duration
+            );
+        }
+        assert_eq!(format_duration(Duration::from_millis(1500)), "1500ms");
+        assert_eq!(format_duration(Duration::from_micros(2500)), "2500us");
+    }
+
+    #[test]
+    fn round_trips_warmup() {
+        for warmup in [
+            Warmup::Iterations(3),
+            Warmup::Time(Duration::from_secs(1)),
+            Warmup::Time(Duration::from_micros(1500)),
+        ] {
+            assert_eq!(warmup.to_string().parse::<Warmup>().unwrap(), warmup);
+        }
+    }
+
+    #[test]
+    fn round_trips_config_args() {
+        let config = BenchConfig {
+            budget: Duration::from_millis(2500),
+            min_samples: 3,
+            max_samples: 50,
+            explicit: ExplicitSettings {
+                budget: true,
+                min_samples: true,
+                max_samples: true,
+                ..ExplicitSettings::default()
+            },
+            ..BenchConfig::default()
+        };
+        let args = config.to_args();
+        assert!(args.windows(2).any(|x| x == ["--budget", "2500ms"]));
+        assert!(args.windows(2).any(|x| x == ["--min-samples", "3"]));
+        assert!(args.windows(2).any(|x| x == ["--max-samples", "50"]));
+        // settings left at their defaults are not mirrored, the solution may override them.
+        assert!(!args.contains(&"--warmup".into()));
+        assert_eq!(parse_samples("0").is_err(), true);
+    }
+
+    #[test]
+    fn applies_day_overrides() {
+        let overrides = r#"{ "07": { "budget": "5s", "max_samples": 20, "warmup": "1" } }"#;
+
+        let config = BenchConfig::default()
+            .with_override(overrides, day!(7))
+            .unwrap();
+        assert_eq!(config.budget, Duration::from_secs(5));
+        assert_eq!(config.min_samples, 10);
+        assert_eq!(config.max_samples, 20);
+        assert_eq!(config.warmup, Warmup::Iterations(1));
+
+        let config = BenchConfig::default()
+            .with_override(overrides, day!(8))
+            .unwrap();
+        assert_eq!(config, BenchConfig::default());
+    }
+
+    #[test]
+    fn prefers_explicit_flags_over_overrides() {
+        let overrides = r#"{ "07": { "budget": "5s", "warmup": "1" } }"#;
+        let config = BenchConfig {
+            budget: Duration::from_millis(200),
+            explicit: ExplicitSettings {
+                budget: true,
+                ..ExplicitSettings::default()
+            },
+            ..BenchConfig::default()
+        };
+
+        let config = config.with_override(overrides, day!(7)).unwrap();
+        assert_eq!(config.budget, Duration::from_millis(200));
+        assert_eq!(config.warmup, Warmup::Iterations(1));
+    }
+
+    #[test]
+    fn rejects_invalid_overrides() {
+        let overrides = r#"{ "07": { "min_samples": 30, "max_samples": 20 } }"#;
+        assert!(
+            BenchConfig::default()
+                .with_override(overrides, day!(7))
+                .is_err()
+        );
+
+        let overrides = r#"{ "07": { "budget": 5 } }"#;
+        assert!(
+            BenchConfig::default()
+                .with_override(overrides, day!(7))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn reads_timeout_overrides() {
+        let overrides =
+            r#"{ "07": { "budget": "5s", "timeout": "120s" }, "08": { "budget": "5s" } }"#;
+        assert_eq!(
+            parse_timeout_override(overrides, day!(7)).unwrap(),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(parse_timeout_override(overrides, day!(8)).unwrap(), None);
+        assert_eq!(parse_timeout_override(overrides, day!(9)).unwrap(), None);
+
+        // the bench settings of a day are not affected by its timeout.
+        let config = BenchConfig::default()
+            .with_override(overrides, day!(7))
+            .unwrap();
+        assert_eq!(config.budget, Duration::from_secs(5));
+
+        let overrides = r#"{ "07": { "timeout": 120 } }"#;
+        assert!(parse_timeout_override(overrides, day!(7)).is_err());
+    }
+
+    #[test]
+    fn tukey_rejects_outliers() {
+        let samples = nanos(&[10, 11, 12, 11, 10, 12, 11, 500]);
+        let kept = OutlierFilter::Tukey.apply(&samples);
+        assert_eq!(kept.len(), 7);
+        assert!(!kept.contains(&Duration::from_nanos(500)));
+    }
+
+    #[test]
+    fn mad_rejects_outliers() {
+        let samples = nanos(&[10, 11, 12, 11, 10, 12, 11, 500]);
+        let kept = OutlierFilter::Mad.apply(&samples);
+        assert_eq!(kept.len(), 7);
+        assert!(!kept.contains(&Duration::from_nanos(500)));
+    }
+
+    #[test]
+    fn keeps_samples_without_spread() {
+        let samples = nanos(&[10, 10, 10, 10, 10, 10, 10, 20]);
+        assert_eq!(OutlierFilter::Tukey.apply(&samples).len(), 8);
+        assert_eq!(OutlierFilter::Mad.apply(&samples).len(), 8);
+    }
+
+    #[test]
+    fn none_keeps_all_samples() {
+        let samples = nanos(&[10, 11, 500]);
+        assert_eq!(OutlierFilter::None.apply(&samples).len(), 3);
+    }
+}
@@ -1,5 +1,34 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::{process, time::Duration};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::run_multi::{
+    RunMultiOptions, needs_profile_switch, run_multi, switch_profile,
+};
+use crate::template::{PuzzleId, Year, all_days, bench::BenchConfig, runner::Solution};
+
+/// Runs every day of `year`.
+pub fn handle(
+    year: Year,
+    is_release: bool,
+    jobs: usize,
+    timeout: Option<Duration>,
+    solutions: &[Solution],
+) {
+    if needs_profile_switch(solutions, is_release) {
+        switch_profile(is_release);
+    }
+
+    let options = RunMultiOptions {
+        is_release,
+        is_timed: false,
+        bench: BenchConfig::default(),
+        jobs,
+        timeout,
+    };
+
+    let puzzles = all_days().map(|day| PuzzleId::new(year, day)).collect();
+    let summary = run_multi(&puzzles, &options, solutions);
+
+    if summary.has_failures() {
+        process::exit(1);
+    }
 }
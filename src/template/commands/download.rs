@@ -1,16 +1,19 @@
-use crate::template::{Day, aoc_cli};
+use crate::template::{PuzzleId, backend};
 use std::process;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!(
-            "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
-        );
+pub fn handle(puzzle: PuzzleId) {
+    let backend = backend::select().unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    });
+
+    if let Err(e) = backend::create_data_dirs(puzzle) {
+        eprintln!("Failed to create data directories: {e}");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    if let Err(e) = backend.download(puzzle) {
+        eprintln!("{e}");
         process::exit(1);
     };
 }
@@ -0,0 +1,110 @@
+use std::{fs, path::Path, process};
+
+use super::suggest;
+use crate::template::{
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, PuzzleId, backend, data_dir, example_manifest::Manifest,
+    examples,
+};
+
+pub fn handle(puzzle: PuzzleId, overwrite: bool) {
+    let day = puzzle.day;
+    let puzzle_path = backend::puzzle_path(puzzle);
+
+    let markdown = fs::read_to_string(&puzzle_path).unwrap_or_else(|e| {
+        eprintln!("Failed to read puzzle description \"{puzzle_path}\": {e}");
+        eprintln!("Type {} to download it first.", suggest("download", puzzle));
+        process::exit(1);
+    });
+
+    let examples = examples::extract(&markdown);
+
+    let Some(input) = examples.input else {
+        eprintln!("Could not find an example in \"{puzzle_path}\".");
+        process::exit(1);
+    };
+
+    let files = [
+        format!("{day}.txt"),
+        if examples.input_part_two.is_some() {
+            format!("{day}-2.txt")
+        } else {
+            format!("{day}.txt")
+        },
+    ];
+
+    let examples_dir = data_dir(puzzle.year).join("examples");
+    write_example(&examples_dir.join(&files[0]), &input, overwrite);
+
+    if let Some(input) = examples.input_part_two {
+        write_example(&examples_dir.join(&files[1]), &input, overwrite);
+    }
+
+    println!("---");
+    for (index, answer) in examples.answers.iter().enumerate() {
+        let part = index + 1;
+        match answer {
+            Some(answer) => println!(
+                "Expected answer of the part {part} example: {ANSI_BOLD}{answer}{ANSI_RESET} {ANSI_ITALIC}(guessed from the description){ANSI_RESET}"
+            ),
+            None => println!("Expected answer of the part {part} example: not found"),
+        }
+    }
+
+    update_manifest(puzzle, &files, &examples.answers);
+}
+
+/// Adds the guessed answers to the example manifest, without replacing answers that are already known.
+fn update_manifest(puzzle: PuzzleId, files: &[String; 2], answers: &[Option<String>; 2]) {
+    let path = Manifest::path(puzzle);
+
+    let mut manifest = match Manifest::read(puzzle) {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            eprintln!("Failed to read {}: {e}", path.display());
+            return;
+        }
+    };
+
+    let mut changed = false;
+    for (index, answer) in answers.iter().enumerate() {
+        if let Some(answer) = answer {
+            let part = u8::try_from(index + 1).unwrap();
+            changed |= manifest.fill_answer(part, &files[index], answer);
+        }
+    }
+
+    if !changed {
+        return;
+    }
+
+    match manifest.write(puzzle) {
+        Ok(()) => println!("Stored the expected answers in \"{}\"", path.display()),
+        Err(e) => eprintln!("Failed to write {}: {e}", path.display()),
+    }
+}
+
+/// Writes an example file unless it already holds an example.
+fn write_example(path: &Path, input: &str, overwrite: bool) {
+    // `scaffold` creates empty example files, these are always replaced.
+    let has_content = fs::read_to_string(path).is_ok_and(|x| !x.trim().is_empty());
+
+    if has_content && !overwrite {
+        println!(
+            "Skipped \"{}\", it is not empty. Use `--overwrite` to replace it.",
+            path.display()
+        );
+        return;
+    }
+
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+
+    match fs::write(path, input) {
+        Ok(()) => println!("Wrote example to \"{}\"", path.display()),
+        Err(e) => {
+            eprintln!("Failed to write example file: {e}");
+            process::exit(1);
+        }
+    }
+}
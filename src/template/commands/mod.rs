@@ -1,6 +1,20 @@
 pub mod all;
 pub mod download;
+pub mod examples;
 pub mod read;
+pub mod run_day;
 pub mod scaffold;
 pub mod solve;
 pub mod time;
+
+use crate::template::{PuzzleId, Year};
+
+/// Formats a command of a puzzle that is suggested to the user, e.g. `cargo solve 01`. The year is only passed if it
+/// differs from the default year, see [`Year::from_env`].
+fn suggest(command: &str, puzzle: PuzzleId) -> String {
+    if Year::from_env() == Ok(puzzle.year) {
+        format!("`cargo {command} {}`", puzzle.day)
+    } else {
+        format!("`cargo {command} {} --year {}`", puzzle.day, puzzle.year)
+    }
+}
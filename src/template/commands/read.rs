@@ -1,17 +1,20 @@
 use std::process;
 
-use crate::template::{Day, aoc_cli};
+use crate::template::{PuzzleId, backend};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!(
-            "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
-        );
+pub fn handle(puzzle: PuzzleId) {
+    let backend = backend::select().unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    });
+
+    if let Err(e) = backend::create_data_dirs(puzzle) {
+        eprintln!("Failed to create data directories: {e}");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    if let Err(e) = backend.read(puzzle) {
+        eprintln!("{e}");
         process::exit(1);
     };
 }
@@ -0,0 +1,16 @@
+use std::process;
+
+use crate::template::{
+    PuzzleId,
+    runner::{RunOptions, Solution},
+};
+
+/// Runs a single puzzle compiled into the runner binary. Used by `run_multi` to execute days in child processes.
+pub fn handle(puzzle: PuzzleId, options: &RunOptions, solutions: &[Solution]) {
+    let Some(solution) = solutions.iter().find(|s| s.puzzle == puzzle) else {
+        eprintln!("Puzzle {puzzle} is not compiled into the runner binary.");
+        process::exit(1);
+    };
+
+    (solution.run)(options);
+}
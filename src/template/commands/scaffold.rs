@@ -1,15 +1,20 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::{Path, PathBuf},
     process,
 };
 
-use crate::template::Day;
+use super::suggest;
+use crate::template::{
+    PuzzleId, backend, data_dir,
+    example_manifest::{Example, Manifest},
+};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
+fn safe_create_file(path: &Path, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
     if overwrite {
         file.create(true);
@@ -19,7 +24,7 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
+fn create_file(path: &Path) -> Result<File, std::io::Error> {
     OpenOptions::new()
         .write(true)
         .create(true)
@@ -27,10 +32,20 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+pub fn handle(puzzle: PuzzleId, overwrite: bool) {
+    let day = puzzle.day;
+    let input_path = PathBuf::from(backend::input_path(puzzle));
+    let example_path = data_dir(puzzle.year)
+        .join("examples")
+        .join(format!("{day}.txt"));
+    let module_path = Path::new("src").join("bin").join(format!("{puzzle}.rs"));
+
+    for dir in [&input_path, &example_path].map(|x| x.parent().unwrap()) {
+        if let Err(e) = fs::create_dir_all(dir) {
+            eprintln!("Failed to create directory \"{}\": {e}", dir.display());
+            process::exit(1);
+        }
+    }
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
@@ -46,7 +61,7 @@ pub fn handle(day: Day, overwrite: bool) {
             .as_bytes(),
     ) {
         Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+            println!("Created module file \"{}\"", module_path.display());
         }
         Err(e) => {
             eprintln!("Failed to write module contents: {e}");
@@ -56,7 +71,7 @@ pub fn handle(day: Day, overwrite: bool) {
 
     match create_file(&input_path) {
         Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+            println!("Created empty input file \"{}\"", input_path.display());
         }
         Err(e) => {
             eprintln!("Failed to create input file: {e}");
@@ -66,7 +81,7 @@ pub fn handle(day: Day, overwrite: bool) {
 
     match create_file(&example_path) {
         Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+            println!("Created empty example file \"{}\"", example_path.display());
         }
         Err(e) => {
             eprintln!("Failed to create example file: {e}");
@@ -74,6 +89,35 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     }
 
+    let manifest_path = Manifest::path(puzzle);
+
+    if manifest_path.exists() {
+        println!(
+            "Kept existing example manifest \"{}\"",
+            manifest_path.display()
+        );
+    } else {
+        let manifest = Manifest {
+            examples: [1, 2]
+                .map(|part| Example {
+                    file: format!("{day}.txt"),
+                    part,
+                    answer: None,
+                })
+                .to_vec(),
+        };
+
+        match manifest.write(puzzle) {
+            Ok(()) => {
+                println!("Created example manifest \"{}\"", manifest_path.display());
+            }
+            Err(e) => {
+                eprintln!("Failed to create example manifest: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    println!("🎄 Type {} to run your solution.", suggest("solve", puzzle));
 }
@@ -1,9 +1,16 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::{PuzzleId, runner::RunOptions};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+pub fn handle(
+    puzzle: PuzzleId,
+    release: bool,
+    dhat: bool,
+    memory: bool,
+    options: &RunOptions,
+    examples: bool,
+) {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.to_string()];
 
     if dhat {
         cmd_args.extend([
@@ -16,13 +23,19 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push("--release".to_string());
     }
 
+    if memory {
+        cmd_args.extend(["--features".to_string(), "memory-stats".to_string()]);
+    }
+
     cmd_args.push("--".to_string());
 
-    if let Some(submit_part) = submit_part {
-        cmd_args.push("--submit".to_string());
-        cmd_args.push(submit_part.to_string());
+    // the solution binary only runs the examples in this mode, other options do not apply.
+    if examples {
+        cmd_args.push("--examples".to_string());
     }
 
+    cmd_args.extend(options.to_args());
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -30,5 +43,10 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    let status = cmd.wait().unwrap();
+
+    // failing examples are reported through the exit code.
+    if examples && !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
 }
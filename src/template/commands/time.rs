@@ -1,32 +1,110 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, process};
 
-use crate::template::run_multi::run_multi;
+use crate::template::compare::{compare, print_deltas};
+use crate::template::run_multi::{
+    RunMultiOptions, needs_profile_switch, run_multi, switch_profile,
+};
+use crate::template::runner::Solution;
 use crate::template::timings::Timings;
-use crate::template::{Day, all_days, readme_benchmarks};
+use crate::template::{Day, PuzzleId, Year, all_days, history, html_report, readme_benchmarks};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+/// What `cargo time` does besides benching.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TimeOptions {
+    /// Bench all days of the year, including the ones that have complete stored timings.
+    pub run_all: bool,
+    /// Store the timings in `data/timings.json` and the readme.
+    pub store: bool,
+    pub allow_parallel: bool,
+    /// Compare with the stored timings and fail if a step regressed by more than this many percent.
+    pub compare_threshold: Option<f64>,
+    /// Write an HTML report of the timings to `target/`.
+    pub report: bool,
+}
+
+/// Benches a day of `year`, or the days of `year` that are selected by the options.
+pub fn handle(
+    year: Year,
+    day: Option<Day>,
+    time_options: &TimeOptions,
+    options: &RunMultiOptions,
+    solutions: &[Solution],
+) {
+    let TimeOptions {
+        run_all,
+        store,
+        allow_parallel,
+        compare_threshold,
+        report,
+    } = *time_options;
+
+    let jobs = options.jobs;
+
+    if jobs > 1 && !allow_parallel {
+        eprintln!(
+            "Running days in parallel skews benchmarks. Pass `--allow-parallel` to run them with {jobs} jobs anyway."
+        );
+        process::exit(1);
+    }
+
+    if needs_profile_switch(solutions, true) {
+        switch_profile(true);
+    }
+
+    // read before any file is written, storing the timings would mark the working tree as dirty otherwise.
+    let commit = history::current_commit();
     let stored_timings = Timings::read_from_file();
 
+    let days_of_year = || all_days().map(|day| PuzzleId::new(year, day));
+
     let days_to_run = day.map_or_else(
         || {
             if run_all {
-                all_days().collect()
+                days_of_year().collect()
+            } else if compare_threshold.is_some() {
+                // when comparing, re-run every day of the year that has stored timings.
+                stored_timings
+                    .data
+                    .iter()
+                    .map(|t| t.puzzle)
+                    .filter(|puzzle| puzzle.year == year)
+                    .collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
+                days_of_year()
+                    .filter(|puzzle| !stored_timings.is_day_complete(*puzzle))
                     .collect()
             }
         },
-        |day| HashSet::from([day]),
+        |day| HashSet::from([PuzzleId::new(year, day)]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let timings = run_multi(&days_to_run, options, solutions).timings.unwrap();
+
+    let regressions = compare_threshold.map_or(0, |threshold| {
+        print_deltas(&compare(&stored_timings, &timings), threshold)
+    });
+
+    if report {
+        match html_report::write(&timings, &stored_timings) {
+            Ok(path) => println!("\nWrote benchmark report to {path}."),
+            Err(e) => eprintln!("Failed to write benchmark report: {e}"),
+        }
+    }
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
         merged_timings.store_file().unwrap();
 
+        let profile = if options.is_release {
+            "release"
+        } else {
+            "debug"
+        };
+        if let Err(e) = history::append(&timings, commit, profile) {
+            eprintln!("Failed to append to timing history: {e}");
+        }
+
         println!();
         match readme_benchmarks::update(merged_timings) {
             Ok(()) => {
@@ -37,4 +115,19 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             }
         }
     }
+
+    if regressions > 0 {
+        process::exit(1);
+    }
+}
+
+/// Prints how the timings of a day evolved across the commits they were stored at.
+pub fn handle_history(puzzle: PuzzleId) {
+    match history::read_day(puzzle) {
+        Ok(entries) => history::print(puzzle, &entries),
+        Err(e) => {
+            eprintln!("Failed to read timing history: {e}");
+            process::exit(1);
+        }
+    }
 }
@@ -0,0 +1,180 @@
+/// Compares freshly measured timings against the stored ones to detect regressions.
+use std::time::Duration;
+
+use crate::template::timings::{PartTiming, Timing, Timings};
+use crate::template::{ANSI_BOLD, ANSI_GREEN, ANSI_ITALIC, ANSI_RED, ANSI_RESET, PuzzleId};
+
+/// Change of the median duration of a single step between the stored and the new timings.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Delta {
+    pub puzzle: PuzzleId,
+    /// Label of the step, e.g. `Part 1`.
+    pub step: &'static str,
+    pub stored: Duration,
+    pub measured: Duration,
+}
+
+impl Delta {
+    /// Relative change in percent, positive if the step became slower.
+    pub fn percent(&self) -> f64 {
+        let stored = self.stored.as_secs_f64();
+        if stored == 0_f64 {
+            return 0_f64;
+        }
+        (self.measured.as_secs_f64() - stored) / stored * 100_f64
+    }
+
+    /// Whether the step became slower by more than `threshold` percent.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.percent() > threshold
+    }
+}
+
+/// Collects the deltas of all steps that are present in both the stored and the new timings.
+pub fn compare(stored: &Timings, measured: &Timings) -> Vec<Delta> {
+    let mut deltas = vec![];
+
+    for timing in &measured.data {
+        let Some(stored_timing) = stored.data.iter().find(|t| t.puzzle == timing.puzzle) else {
+            continue;
+        };
+
+        for (step, get) in STEPS {
+            let (Some(stored), Some(measured)) = (
+                get(stored_timing).and_then(PartTiming::median),
+                get(timing).and_then(PartTiming::median),
+            ) else {
+                continue;
+            };
+
+            deltas.push(Delta {
+                puzzle: timing.puzzle,
+                step,
+                stored,
+                measured,
+            });
+        }
+    }
+
+    deltas
+}
+
+pub type StepGetter = fn(&Timing) -> Option<&PartTiming>;
+
+/// Label and accessor of every step of a day, in display order.
+pub static STEPS: [(&str, StepGetter); 3] = [
+    ("Parse", |t| t.parse.as_ref()),
+    ("Part 1", |t| t.part_1.as_ref()),
+    ("Part 2", |t| t.part_2.as_ref()),
+];
+
+/// Prints every delta with a colored indicator and returns the number of regressions.
+pub fn print_deltas(deltas: &[Delta], threshold: f64) -> usize {
+    println!(
+        "\n{ANSI_BOLD}Comparison with stored timings{ANSI_RESET} {ANSI_ITALIC}(threshold {threshold}%){ANSI_RESET}"
+    );
+
+    if deltas.is_empty() {
+        println!("No stored timings to compare with.");
+        return 0;
+    }
+
+    for delta in deltas {
+        let percent = delta.percent();
+
+        let indicator = if delta.is_regression(threshold) {
+            format!("{ANSI_RED}▲ {percent:+.1}% slower{ANSI_RESET}")
+        } else if percent < -threshold {
+            format!("{ANSI_GREEN}▼ {percent:+.1}% faster{ANSI_RESET}")
+        } else {
+            format!("= {percent:+.1}%")
+        };
+
+        println!(
+            "{} Day {} {}: {:.1?} → {:.1?} {indicator}",
+            delta.puzzle.year, delta.puzzle.day, delta.step, delta.stored, delta.measured
+        );
+    }
+
+    let regressions = deltas.iter().filter(|x| x.is_regression(threshold)).count();
+
+    if regressions > 0 {
+        println!("{ANSI_RED}{ANSI_BOLD}{regressions} step(s) regressed.{ANSI_RESET}");
+    }
+
+    regressions
+}
+
+/// Parses a threshold in percent, e.g. `10` or `10%`.
+pub fn parse_threshold(s: &str) -> Result<f64, String> {
+    s.strip_suffix('%')
+        .unwrap_or(s)
+        .parse::<f64>()
+        .ok()
+        .filter(|x| *x >= 0_f64)
+        .ok_or(format!(
+            "invalid threshold \"{s}\", expecting a percentage such as `10%`"
+        ))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Delta, compare, parse_threshold};
+    use crate::{
+        day,
+        template::{
+            PuzzleId,
+            timings::{Timing, Timings},
+        },
+        year,
+    };
+    use std::time::Duration;
+
+    fn timings(part_1: &str, part_2: Option<&str>) -> Timings {
+        Timings {
+            data: vec![Timing {
+                puzzle: PuzzleId::new(year!(2025), day!(1)),
+                parse: None,
+                part_1: Some(part_1.into()),
+                part_2: part_2.map(Into::into),
+                total_nanos: 0_f64,
+                timed_out: false,
+                errored: vec![],
+                bench: None,
+            }],
+        }
+    }
+
+    #[test]
+    fn compares_common_steps() {
+        let deltas = compare(&timings("10.0µs", Some("1.0ms")), &timings("12.0µs", None));
+        assert_eq!(
+            deltas,
+            vec![Delta {
+                puzzle: PuzzleId::new(year!(2025), day!(1)),
+                step: "Part 1",
+                stored: Duration::from_micros(10),
+                measured: Duration::from_micros(12),
+            }]
+        );
+        assert!((deltas[0].percent() - 20_f64).abs() < 1e-9);
+        assert!(deltas[0].is_regression(10_f64));
+        assert!(!deltas[0].is_regression(25_f64));
+    }
+
+    #[test]
+    fn ignores_days_without_stored_timings() {
+        let stored = Timings::default();
+        assert!(compare(&stored, &timings("1ms", Some("2ms"))).is_empty());
+    }
+
+    #[test]
+    fn parses_thresholds() {
+        assert_eq!(parse_threshold("10").unwrap(), 10_f64);
+        assert_eq!(parse_threshold("2.5%").unwrap(), 2.5);
+        assert!(parse_threshold("-1").is_err());
+        assert!(parse_threshold("fast").is_err());
+    }
+}
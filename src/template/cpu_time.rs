@@ -0,0 +1,123 @@
+/// CPU time (user plus system) of the runner and of the days it executes as child processes, read via `getrusage(2)`
+/// and `wait4(2)` on Linux. On other platforms, no CPU time is recorded.
+use std::io;
+use std::process::Child;
+use std::time::Duration;
+
+/// CPU time used by all threads of this process so far.
+#[must_use]
+pub fn of_self() -> Option<Duration> {
+    #[cfg(target_os = "linux")]
+    {
+        let mut usage = linux::empty_usage();
+        let result = unsafe { libc::getrusage(libc::RUSAGE_SELF, &raw mut usage) };
+        (result == 0).then(|| linux::total(&usage))
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+/// A child process that was reaped by [`reap`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Exited {
+    /// CPU time used by the child and the processes it waited for, `None` if it cannot be measured.
+    pub cpu_time: Option<Duration>,
+}
+
+/// Reaps `child` once it has exited, waiting for it if `block` is set. Returns `None` if the child is still running.
+///
+/// Replaces [`Child::wait`] and [`Child::try_wait`], which do not report the resource usage of the child. The child
+/// must not be waited for through these afterwards.
+pub fn reap(child: &mut Child, block: bool) -> io::Result<Option<Exited>> {
+    #[cfg(target_os = "linux")]
+    {
+        let pid = libc::pid_t::try_from(child.id()).map_err(io::Error::other)?;
+        let flags = if block { 0 } else { libc::WNOHANG };
+
+        loop {
+            let mut status = 0;
+            let mut usage = linux::empty_usage();
+            let reaped = unsafe { libc::wait4(pid, &raw mut status, flags, &raw mut usage) };
+
+            match reaped {
+                0 => return Ok(None),
+                -1 => {
+                    let e = io::Error::last_os_error();
+                    if e.kind() != io::ErrorKind::Interrupted {
+                        return Err(e);
+                    }
+                }
+                _ => {
+                    return Ok(Some(Exited {
+                        cpu_time: Some(linux::total(&usage)),
+                    }));
+                }
+            }
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let status = if block {
+            Some(child.wait()?)
+        } else {
+            child.try_wait()?
+        };
+        Ok(status.map(|_| Exited { cpu_time: None }))
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::time::Duration;
+
+    pub fn empty_usage() -> libc::rusage {
+        // SAFETY: `rusage` is a plain C struct of integers, for which all zeroes is a valid value.
+        unsafe { std::mem::zeroed() }
+    }
+
+    /// User plus system time of a resource usage.
+    pub fn total(usage: &libc::rusage) -> Duration {
+        let duration = |time: libc::timeval| {
+            Duration::from_secs(u64::try_from(time.tv_sec).unwrap_or_default())
+                + Duration::from_micros(u64::try_from(time.tv_usec).unwrap_or_default())
+        };
+
+        duration(usage.ru_utime) + duration(usage.ru_stime)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(feature = "test_lib", target_os = "linux"))]
+mod tests {
+    use super::{of_self, reap};
+    use std::{hint::black_box, process::Command, time::Instant};
+
+    #[test]
+    fn measures_own_cpu_time() {
+        let before = of_self().unwrap();
+
+        let timer = Instant::now();
+        let mut x = 0_u64;
+        while timer.elapsed().as_millis() < 50 {
+            x = black_box(x.wrapping_add(1));
+        }
+
+        assert!(of_self().unwrap() > before);
+    }
+
+    #[test]
+    fn reaps_children_with_their_cpu_time() {
+        let mut child = Command::new("sh")
+            .args(["-c", "i=0; while [ $i -lt 20000 ]; do i=$((i+1)); done"])
+            .spawn()
+            .unwrap();
+
+        let exited = reap(&mut child, true).unwrap().unwrap();
+        assert!(exited.cpu_time.unwrap().as_nanos() > 0);
+    }
+}
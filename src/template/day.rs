@@ -32,7 +32,7 @@ impl Day {
     }
 
     /// Converts the [`Day`] into an [`u8`].
-    pub fn into_inner(self) -> u8 {
+    pub const fn into_inner(self) -> u8 {
         self.0
     }
 }
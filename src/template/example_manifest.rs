@@ -0,0 +1,434 @@
+/// Manifest of the examples of a day and their expected answers, used to generate example tests and by
+/// `cargo solve <day> --examples`.
+///
+/// The manifest of a day lives next to its example files, e.g. `data/2024/examples/01.json`:
+///
+/// ```json
+/// [
+///   { "file": "01.txt", "part": 1, "answer": "142" },
+///   { "file": "01-2.txt", "part": 2, "answer": "281" },
+///   { "file": "01-3.txt", "part": 2, "answer": null }
+/// ]
+/// ```
+///
+/// An entry without an answer is listed but not checked.
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::{env, fs, io, process};
+
+use tinyjson::JsonValue;
+
+use crate::template::answers::Verdict;
+use crate::template::outcome::PartOutcome;
+use crate::template::{ANSI_BOLD, ANSI_RED, ANSI_RESET, PuzzleId, Year, data_dir, ocr};
+
+/// Solves a part for an example input, generated by the [`solution!`](crate::solution) macro.
+/// Returns [`PartOutcome::Unsolved`] if the part is not implemented.
+pub type SolveExample = fn(u8, &str) -> PartOutcome;
+
+/// A single example input and the expected answer of a part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Example {
+    /// Name of the example file, relative to the examples directory of the year.
+    pub file: String,
+    pub part: u8,
+    pub answer: Option<String>,
+}
+
+impl Example {
+    /// Reads the input of the example from the examples directory of `year`.
+    pub fn read_input(&self, year: Year) -> Result<String, io::Error> {
+        fs::read_to_string(examples_dir(year).join(&self.file))
+    }
+
+    #[must_use]
+    pub fn check(&self, answer: Option<&str>) -> Verdict {
+        match (&self.answer, answer) {
+            (None, _) => Verdict::Unknown,
+            (Some(expected), Some(answer)) if *expected == ocr::read(answer) => Verdict::Match,
+            (Some(expected), _) => Verdict::Mismatch {
+                expected: expected.clone(),
+            },
+        }
+    }
+}
+
+/// All examples of a day, in the order of the manifest.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Manifest {
+    pub examples: Vec<Example>,
+}
+
+impl Manifest {
+    #[must_use]
+    pub fn path(puzzle: PuzzleId) -> PathBuf {
+        examples_dir(puzzle.year).join(format!("{}.json", puzzle.day))
+    }
+
+    /// Reads the manifest of a puzzle. If not present, returns an empty manifest.
+    pub fn read(puzzle: PuzzleId) -> Result<Self, String> {
+        match fs::read_to_string(Self::path(puzzle)) {
+            Ok(content) => content.parse(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    pub fn write(&self, puzzle: PuzzleId) -> Result<(), io::Error> {
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(Self::path(puzzle))?;
+        json.format_to(&mut file)
+    }
+
+    pub fn for_part(&self, part: u8) -> impl Iterator<Item = &Example> {
+        self.examples.iter().filter(move |x| x.part == part)
+    }
+
+    /// Points the first entry of a part to `file` and `answer`, if that entry has no answer yet. Adds an entry if the
+    /// part has none. Returns whether the manifest changed.
+    pub fn fill_answer(&mut self, part: u8, file: &str, answer: &str) -> bool {
+        if let Some(example) = self.examples.iter_mut().find(|x| x.part == part) {
+            if example.answer.is_some() {
+                return false;
+            }
+            example.file = file.into();
+            example.answer = Some(answer.into());
+        } else {
+            self.examples.push(Example {
+                file: file.into(),
+                part,
+                answer: Some(answer.into()),
+            });
+        }
+        true
+    }
+}
+
+fn examples_dir(year: Year) -> PathBuf {
+    data_dir(year).join("examples")
+}
+
+/// Whether a solution binary was asked to run its examples (`--examples`).
+#[must_use]
+pub fn is_requested() -> bool {
+    env::args().any(|x| x == "--examples")
+}
+
+/// Runs every example of the manifest and prints whether it passed. Exits with an error if one did not.
+pub fn run(puzzle: PuzzleId, solve: SolveExample) {
+    let manifest = Manifest::read(puzzle).unwrap_or_else(|e| {
+        eprintln!("Failed to read {}: {e}", Manifest::path(puzzle).display());
+        process::exit(1);
+    });
+
+    if manifest.examples.is_empty() {
+        eprintln!(
+            "No examples listed in {}, see the readme for its format.",
+            Manifest::path(puzzle).display()
+        );
+        process::exit(1);
+    }
+
+    let mut failures = 0;
+
+    for example in &manifest.examples {
+        let label = format!("Part {} · {}", example.part, example.file);
+
+        let outcome = match example.read_input(puzzle.year) {
+            Ok(input) => solve(example.part, &input),
+            Err(e) => {
+                println!("{label}: {ANSI_RED}failed to read input: {e}{ANSI_RESET}");
+                failures += 1;
+                continue;
+            }
+        };
+
+        let verdict = example.check(outcome.answer());
+        if matches!(verdict, Verdict::Mismatch { .. }) {
+            failures += 1;
+        }
+
+        match outcome {
+            PartOutcome::Solved(answer) if answer.contains('\n') => {
+                println!("{label}: ▼ {verdict}\n{answer}");
+            }
+            PartOutcome::Solved(answer) => {
+                println!("{label}: {ANSI_BOLD}{answer}{ANSI_RESET} {verdict}");
+            }
+            PartOutcome::Unsolved => println!("{label}: ✖ {verdict}"),
+            PartOutcome::Errored(error) => {
+                println!("{label}: ✖ {verdict}");
+                for line in error.lines() {
+                    println!("  {ANSI_RED}{line}{ANSI_RESET}");
+                }
+            }
+        }
+    }
+
+    if failures > 0 {
+        println!(
+            "{ANSI_RED}{ANSI_BOLD}{failures} of {} example(s) failed.{ANSI_RESET}",
+            manifest.examples.len()
+        );
+        process::exit(1);
+    }
+}
+
+/// Asserts that every example of a part matches its expected answer. Used by [`example_tests!`](crate::example_tests).
+///
+/// # Panics
+/// Panics if the manifest cannot be read, if no example of the part has an answer to check or if any example of the
+/// part fails.
+pub fn assert_part(puzzle: PuzzleId, part: u8, solve: SolveExample) {
+    let manifest =
+        Manifest::read(puzzle).unwrap_or_else(|e| panic!("invalid example manifest: {e}"));
+
+    // a test that checks nothing would pass for a part that is not solved yet.
+    assert!(
+        manifest.for_part(part).any(|x| x.answer.is_some()),
+        "no example of part {part} has an answer, add one to {}",
+        Manifest::path(puzzle).display()
+    );
+
+    let failures: Vec<String> = manifest
+        .for_part(part)
+        .filter_map(|example| {
+            let input = example
+                .read_input(puzzle.year)
+                .unwrap_or_else(|e| panic!("could not open example file {}: {e}", example.file));
+            let outcome = solve(part, &input);
+
+            match example.check(outcome.answer()) {
+                Verdict::Mismatch { expected } => Some(format!(
+                    "{}: expected {expected:?}, got {outcome:?}",
+                    example.file
+                )),
+                Verdict::Match | Verdict::Unknown => None,
+            }
+        })
+        .collect();
+
+    assert!(
+        failures.is_empty(),
+        "{} example(s) of part {part} failed:\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
+
+/// Generates a test per part that checks the examples listed in the manifest of the day, see [`Manifest`].
+///
+/// Expects `PUZZLE` and `solve_example` to be in scope, both are created by the [`solution!`](crate::solution) macro.
+///
+/// ```ignore
+/// #[cfg(test)]
+/// mod tests {
+///     use super::*;
+///
+///     advent_of_code::example_tests!();
+/// }
+/// ```
+#[macro_export]
+macro_rules! example_tests {
+    () => {
+        #[test]
+        fn test_part_one_examples() {
+            $crate::template::example_manifest::assert_part(PUZZLE, 1, solve_example);
+        }
+
+        #[test]
+        fn test_part_two_examples() {
+            $crate::template::example_manifest::assert_part(PUZZLE, 2, solve_example);
+        }
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Manifest> for JsonValue {
+    fn from(value: &Manifest) -> Self {
+        let examples = value
+            .examples
+            .iter()
+            .map(|example| {
+                let mut map: HashMap<String, JsonValue> = HashMap::new();
+                map.insert("file".into(), JsonValue::String(example.file.clone()));
+                map.insert("part".into(), JsonValue::Number(example.part.into()));
+                map.insert(
+                    "answer".into(),
+                    example
+                        .answer
+                        .clone()
+                        .map_or(JsonValue::Null, JsonValue::String),
+                );
+                JsonValue::Object(map)
+            })
+            .collect();
+
+        JsonValue::Array(examples)
+    }
+}
+
+impl std::str::FromStr for Manifest {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = s
+            .parse::<JsonValue>()
+            .or(Err("example manifest is not valid JSON."))?;
+
+        let entries = json
+            .get::<Vec<JsonValue>>()
+            .ok_or("Expected the example manifest to be a JSON array.")?;
+
+        let examples = entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                let entry = entry
+                    .get::<HashMap<String, JsonValue>>()
+                    .ok_or(format!("Expected example {index} to be an object."))?;
+
+                let file = entry
+                    .get("file")
+                    .and_then(|x| x.get::<String>())
+                    .ok_or(format!("Expected example {index} to have a \"file\"."))?
+                    .clone();
+
+                let part = match entry.get("part") {
+                    Some(JsonValue::Number(x)) if *x == 1_f64 => 1,
+                    Some(JsonValue::Number(x)) if *x == 2_f64 => 2,
+                    _ => {
+                        return Err(format!(
+                            "Expected the part of example {index} to be 1 or 2."
+                        ));
+                    }
+                };
+
+                let answer = match entry.get("answer") {
+                    None | Some(JsonValue::Null) => None,
+                    Some(JsonValue::String(x)) => Some(x.clone()),
+                    // numbers are accepted for convenience when editing by hand.
+                    #[allow(clippy::cast_possible_truncation)]
+                    Some(JsonValue::Number(x)) if x.fract() == 0_f64 => {
+                        Some((*x as i64).to_string())
+                    }
+                    Some(_) => {
+                        return Err(format!(
+                            "Expected the answer of example {index} to be a string."
+                        ));
+                    }
+                };
+
+                Ok(Example { file, part, answer })
+            })
+            .collect::<Result<_, String>>()?;
+
+        Ok(Manifest { examples })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Example, Manifest, assert_part};
+    use crate::template::{PuzzleId, answers::Verdict};
+    use crate::{day, year};
+    use tinyjson::JsonValue;
+
+    fn example(file: &str, part: u8, answer: Option<&str>) -> Example {
+        Example {
+            file: file.into(),
+            part,
+            answer: answer.map(Into::into),
+        }
+    }
+
+    #[test]
+    fn parses_manifest() {
+        let manifest: Manifest = r#"[
+            { "file": "01.txt", "part": 1, "answer": 142 },
+            { "file": "01-2.txt", "part": 2, "answer": "281" },
+            { "file": "01-3.txt", "part": 2 }
+        ]"#
+        .parse()
+        .unwrap();
+
+        assert_eq!(
+            manifest.examples,
+            vec![
+                example("01.txt", 1, Some("142")),
+                example("01-2.txt", 2, Some("281")),
+                example("01-3.txt", 2, None),
+            ]
+        );
+        assert_eq!(manifest.for_part(2).count(), 2);
+
+        assert!(r#"{ "file": "01.txt" }"#.parse::<Manifest>().is_err());
+        assert!(r#"[{ "file": "01.txt", "part": 3 }]"#.parse::<Manifest>().is_err());
+        assert!(r#"[{ "part": 1 }]"#.parse::<Manifest>().is_err());
+    }
+
+    #[test]
+    fn round_trips_json() {
+        let manifest = Manifest {
+            examples: vec![
+                example("01.txt", 1, Some("142")),
+                example("01.txt", 2, None),
+            ],
+        };
+        let json = JsonValue::from(&manifest).stringify().unwrap();
+        assert_eq!(json.parse::<Manifest>().unwrap(), manifest);
+    }
+
+    #[test]
+    fn checks_answers() {
+        let known = example("01.txt", 1, Some("142"));
+        assert_eq!(known.check(Some("142")), Verdict::Match);
+        assert_eq!(
+            known.check(None),
+            Verdict::Mismatch {
+                expected: "142".into()
+            }
+        );
+        assert_eq!(
+            example("01.txt", 1, None).check(Some("1")),
+            Verdict::Unknown
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "no example of part 1 has an answer")]
+    fn fails_parts_without_answers() {
+        // no manifest exists for this puzzle, so there is nothing to check.
+        assert_part(
+            PuzzleId::new(year!(2015), day!(1)),
+            1,
+            |_, _| unreachable!(),
+        );
+    }
+
+    #[test]
+    fn fills_missing_answers_only() {
+        let mut manifest = Manifest {
+            examples: vec![example("01.txt", 1, None), example("01.txt", 2, None)],
+        };
+
+        assert!(manifest.fill_answer(1, "01.txt", "142"));
+        assert!(!manifest.fill_answer(1, "01.txt", "143"));
+        assert!(manifest.fill_answer(2, "01-2.txt", "281"));
+
+        assert_eq!(
+            manifest.examples,
+            vec![
+                example("01.txt", 1, Some("142")),
+                example("01-2.txt", 2, Some("281"))
+            ]
+        );
+
+        let mut manifest = Manifest::default();
+        assert!(manifest.fill_answer(2, "01.txt", "281"));
+        assert_eq!(manifest.examples, vec![example("01.txt", 2, Some("281"))]);
+    }
+}
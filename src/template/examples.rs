@@ -0,0 +1,289 @@
+/// Finds the example inputs and the expected example answers in a puzzle description, as downloaded to `data/<year>/puzzles`.
+///
+/// Puzzle descriptions are regular enough for a few heuristics: an example input is a code block that follows a
+/// paragraph such as "For example:", and the answer to an example is usually the last emphasized code span of a part.
+use std::cmp::Reverse;
+
+/// Examples found in a puzzle description.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Examples {
+    /// Example input of part one.
+    pub input: Option<String>,
+    /// Example input of part two, only set if it differs from the one of part one.
+    pub input_part_two: Option<String>,
+    /// Expected answers for the examples, by part.
+    pub answers: [Option<String>; 2],
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct CodeBlock {
+    /// Text of the paragraph that precedes the block.
+    intro: String,
+    content: String,
+}
+
+impl CodeBlock {
+    fn is_introduced_as_example(&self) -> bool {
+        self.intro.to_lowercase().contains("example")
+    }
+
+    /// How likely the block is to be an example input. Blocks scoring zero are not considered at all.
+    fn score(&self) -> usize {
+        let lines = self.content.lines().count();
+        if lines == 0 {
+            return 0;
+        }
+
+        let mut score = lines.min(10);
+        if self.intro.to_lowercase().contains("for example") {
+            score += 20;
+        } else if self.is_introduced_as_example() {
+            score += 10;
+        }
+        score
+    }
+}
+
+/// Extracts the examples of both parts from the markdown of a puzzle description.
+#[must_use]
+pub fn extract(markdown: &str) -> Examples {
+    let (part_one, part_two) = split_parts(markdown);
+
+    let input = best_example(&code_blocks(part_one));
+
+    let input_part_two = part_two
+        .map(code_blocks)
+        .and_then(|blocks| {
+            // part two mostly reuses the first example, so only blocks explicitly introduced as examples count.
+            let blocks: Vec<CodeBlock> = blocks
+                .into_iter()
+                .filter(|x| x.is_introduced_as_example() && x.content.lines().count() > 1)
+                .collect();
+            best_example(&blocks)
+        })
+        .filter(|x| Some(x) != input.as_ref());
+
+    Examples {
+        input,
+        input_part_two,
+        answers: [
+            last_emphasized_code(part_one),
+            part_two.and_then(last_emphasized_code),
+        ],
+    }
+}
+
+/// Splits the description at the heading of part two, if it has been unlocked.
+fn split_parts(markdown: &str) -> (&str, Option<&str>) {
+    let Some(heading) = markdown.find("--- Part Two ---") else {
+        return (markdown, None);
+    };
+    let line_start = markdown[..heading].rfind('\n').map_or(0, |x| x + 1);
+    (&markdown[..line_start], Some(&markdown[heading..]))
+}
+
+/// Collects the fenced code blocks of a section, together with the paragraph preceding each.
+fn code_blocks(section: &str) -> Vec<CodeBlock> {
+    let mut blocks = vec![];
+    let mut paragraph: Vec<&str> = vec![];
+    let mut after_blank_line = false;
+    let mut code: Option<Vec<&str>> = None;
+
+    for line in section.lines() {
+        match &mut code {
+            Some(lines) if line.trim_end() == "```" => {
+                blocks.push(CodeBlock {
+                    intro: paragraph.join(" "),
+                    content: lines.join("\n"),
+                });
+                code = None;
+                paragraph.clear();
+            }
+            Some(lines) => lines.push(line),
+            None if line.trim_start().starts_with("```") => code = Some(vec![]),
+            None if line.trim().is_empty() => after_blank_line = true,
+            None => {
+                // only the paragraph directly before a block introduces it.
+                if after_blank_line {
+                    paragraph.clear();
+                    after_blank_line = false;
+                }
+                paragraph.push(line.trim());
+            }
+        }
+    }
+
+    blocks
+}
+
+fn best_example(blocks: &[CodeBlock]) -> Option<String> {
+    blocks
+        .iter()
+        .enumerate()
+        .filter(|(_, x)| x.score() > 0)
+        // prefer the earliest block on ties, later blocks tend to be intermediate states.
+        .max_by_key(|(index, x)| (x.score(), Reverse(*index)))
+        .map(|(_, x)| format!("{}\n", x.content))
+}
+
+/// Finds the last code span that is also emphasized, written as `` `*x*` `` or `` *`x`* ``, outside of code blocks.
+fn last_emphasized_code(section: &str) -> Option<String> {
+    let mut in_code = false;
+    let mut last = None;
+
+    for line in section.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code || line.starts_with("Your puzzle answer was") {
+            continue;
+        }
+
+        let mut spans = vec![];
+        for (open, close) in [("`*", "*`"), ("*`", "`*")] {
+            let mut offset = 0;
+            while let Some(start) = line[offset..].find(open).map(|x| offset + x) {
+                let content_start = start + open.len();
+                let Some(end) = line[content_start..].find(close).map(|x| content_start + x) else {
+                    break;
+                };
+                let candidate = &line[content_start..end];
+                // reject matches that span from the end of one code span to the start of another.
+                if !candidate.is_empty()
+                    && !candidate.contains(['`', '*'])
+                    && !candidate.contains(char::is_whitespace)
+                {
+                    spans.push((start, candidate));
+                    offset = end + close.len();
+                } else {
+                    offset = start + 1;
+                }
+            }
+        }
+
+        if let Some((_, answer)) = spans.into_iter().max_by_key(|(start, _)| *start) {
+            last = Some(answer.to_string());
+        }
+    }
+
+    last
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Examples, extract, last_emphasized_code};
+
+    const PUZZLE: &str = "## --- Day 1: Trebuchet?! ---
+
+Something is wrong with global snow production.
+
+The newly-improved calibration document consists of lines of text. For example:
+
+```
+1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet
+```
+
+In this example, the calibration values of these four lines are `12`, `38`, `15`, and `77`. Adding these together produces `*142*`.
+
+Consider your entire calibration document. *What is the sum of all of the calibration values?*
+
+Your puzzle answer was `54239`.
+
+## --- Part Two ---
+
+Your calculation isn't quite right. Equipped with this new information, you now need to find the real first and last digit on each line. For example:
+
+```
+two1nine
+eightwothree
+abcone2threexyz
+```
+
+In this example, the calibration values are `29` and `83`. Adding these together produces `*112*`.
+
+What is the sum of all of the calibration values?
+";
+
+    #[test]
+    fn extracts_examples_of_both_parts() {
+        assert_eq!(
+            extract(PUZZLE),
+            Examples {
+                input: Some("1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n".into()),
+                input_part_two: Some("two1nine\neightwothree\nabcone2threexyz\n".into()),
+                answers: [Some("142".into()), Some("112".into())],
+            }
+        );
+    }
+
+    #[test]
+    fn reuses_the_first_example_when_part_two_has_none() {
+        let markdown = "For example:
+
+```
+3   4
+4   3
+```
+
+The total distance is `*11*`.
+
+## --- Part Two ---
+
+Here is the same example list again:
+
+```
+3   4
+4   3
+```
+
+So, for these example lists, the similarity score at the end of this process is `*31*`.
+";
+        let examples = extract(markdown);
+        assert_eq!(examples.input.as_deref(), Some("3   4\n4   3\n"));
+        assert_eq!(examples.input_part_two, None);
+        assert_eq!(examples.answers, [Some("11".into()), Some("31".into())]);
+    }
+
+    #[test]
+    fn prefers_blocks_introduced_as_examples() {
+        let markdown = "The map looks like this:
+
+```
+#
+```
+
+For example, consider this report:
+
+```
+7 6 4
+1 2 7
+```
+
+After this step:
+
+```
+a
+b
+c
+d
+```
+";
+        assert_eq!(extract(markdown).input.as_deref(), Some("7 6 4\n1 2 7\n"));
+    }
+
+    #[test]
+    fn finds_emphasized_code_in_both_notations() {
+        assert_eq!(
+            last_emphasized_code("first `*1*`, then *`2`* and `3`."),
+            Some("2".into())
+        );
+        assert_eq!(last_emphasized_code("a *`1`* and *`2`*"), Some("2".into()));
+        assert_eq!(last_emphasized_code("no answer `4`"), None);
+    }
+}
@@ -0,0 +1,333 @@
+/// Append-only log of every stored benchmark, so that the performance of a day can be followed across commits.
+///
+/// Every line of the history file is a JSON object such as:
+///
+/// ```json
+/// {"commit":"1a2b3c4","timestamp":1733011200,"profile":"release","timing":{"year":"2024","day":"01",...}}
+/// ```
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::process::Command;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tinyjson::JsonValue;
+
+use crate::template::timings::{PartTiming, Timing, Timings};
+use crate::template::{ANSI_BOLD, ANSI_RESET, PuzzleId};
+
+static HISTORY_FILE_PATH: &str = "./data/timings-history.jsonl";
+
+/// A single measurement of a day.
+#[derive(Clone, Debug)]
+pub struct HistoryEntry {
+    /// Short hash of the git commit the measurement was taken at, suffixed with `-dirty` for uncommitted changes.
+    pub commit: Option<String>,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Build profile of the measurement, e.g. `release`.
+    pub profile: String,
+    pub timing: Timing,
+}
+
+/// Appends one entry per day of `timings` to the history file, tagged with `commit`, see [`current_commit`].
+pub fn append(timings: &Timings, commit: Option<String>, profile: &str) -> Result<(), io::Error> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |x| x.as_secs());
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(HISTORY_FILE_PATH)?;
+
+    for timing in &timings.data {
+        let entry = HistoryEntry {
+            commit: commit.clone(),
+            timestamp,
+            profile: profile.into(),
+            timing: timing.clone(),
+        };
+
+        let line = JsonValue::from(&entry)
+            .stringify()
+            .map_err(io::Error::other)?;
+        writeln!(file, "{line}")?;
+    }
+
+    Ok(())
+}
+
+/// Reads all entries of a puzzle from the history file, oldest first. If not present, returns no entries.
+pub fn read_day(puzzle: PuzzleId) -> Result<Vec<HistoryEntry>, String> {
+    let content = match fs::read_to_string(HISTORY_FILE_PATH) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e.to_string()),
+    };
+
+    parse_entries(&content, puzzle)
+}
+
+fn parse_entries(content: &str, puzzle: PuzzleId) -> Result<Vec<HistoryEntry>, String> {
+    let mut entries = vec![];
+
+    for line in content.lines().filter(|line| !line.trim().is_empty()) {
+        let entry = HistoryEntry::from_str(line)?;
+        if entry.timing.puzzle == puzzle {
+            entries.push(entry);
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Prints how the timing of every step of a day evolved across commits.
+pub fn print(puzzle: PuzzleId, entries: &[HistoryEntry]) {
+    let PuzzleId { year, day } = puzzle;
+    println!("{ANSI_BOLD}{year} Day {day}{ANSI_RESET}");
+    println!("------");
+
+    if entries.is_empty() {
+        println!(
+            "No history recorded yet. Run `cargo time {day} --year {year} --store` to record one."
+        );
+        return;
+    }
+
+    let has_parse = entries.iter().any(|x| x.timing.parse.is_some());
+
+    let mut rows: Vec<Vec<String>> = vec![];
+
+    let mut header = vec!["Commit".into(), "Date".into(), "Profile".into()];
+    if has_parse {
+        header.push("Parse".into());
+    }
+    header.extend(["Part 1".into(), "Part 2".into()]);
+    rows.push(header);
+
+    for entry in entries {
+        let mut row = vec![
+            entry.commit.clone().unwrap_or_else(|| "-".into()),
+            format_date(entry.timestamp),
+            entry.profile.clone(),
+        ];
+        if has_parse {
+            row.push(format_part(entry.timing.parse.as_ref()));
+        }
+        row.push(format_part(entry.timing.part_1.as_ref()));
+        row.push(format_part(entry.timing.part_2.as_ref()));
+        rows.push(row);
+    }
+
+    let widths: Vec<usize> = (0..rows[0].len())
+        .map(|col| {
+            rows.iter()
+                .map(|row| row[col].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    for (index, row) in rows.iter().enumerate() {
+        let line: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect();
+
+        if index == 0 {
+            println!("{ANSI_BOLD}{}{ANSI_RESET}", line.join("  ").trim_end());
+        } else {
+            println!("{}", line.join("  ").trim_end());
+        }
+    }
+}
+
+fn format_part(part: Option<&PartTiming>) -> String {
+    part.map_or_else(|| "-".into(), |x| x.duration.clone())
+}
+
+/// Formats a unix timestamp as an UTC date, e.g. `2024-12-01`.
+pub fn format_date(timestamp: u64) -> String {
+    // see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    #[allow(clippy::cast_possible_wrap)]
+    let days = (timestamp / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Returns the short hash of `HEAD`, if the current directory is a git repository.
+pub fn current_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|x| x.status.success())?;
+
+    let hash = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    let is_dirty = Command::new("git")
+        .args(["status", "--porcelain", "--untracked-files=no"])
+        .output()
+        .is_ok_and(|x| !x.stdout.is_empty());
+
+    Some(if is_dirty {
+        format!("{hash}-dirty")
+    } else {
+        hash
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&HistoryEntry> for JsonValue {
+    fn from(value: &HistoryEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "commit".into(),
+            match &value.commit {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert("profile".into(), JsonValue::String(value.profile.clone()));
+        map.insert("timing".into(), JsonValue::from(&value.timing));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl FromStr for HistoryEntry {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = JsonValue::from_str(s).or(Err("history entry is not valid JSON."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        let commit = json
+            .get("commit")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected entry.commit to be null or string.")?
+            .cloned();
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u64)
+            .ok_or("Expected entry.timestamp to be a number.")?;
+
+        let profile = json
+            .get("profile")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected entry.profile to be a string.")?
+            .clone();
+
+        let timing = Timing::try_from(
+            json.get("timing")
+                .ok_or("Expected history entry to have key `timing`.")?,
+        )?;
+
+        Ok(HistoryEntry {
+            commit,
+            timestamp,
+            profile,
+            timing,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{HistoryEntry, format_date, parse_entries};
+    use crate::{
+        day,
+        template::{PuzzleId, timings::Timing},
+        year,
+    };
+    use tinyjson::JsonValue;
+
+    fn get_mock_entry(commit: Option<&str>) -> HistoryEntry {
+        HistoryEntry {
+            commit: commit.map(Into::into),
+            timestamp: 1_733_011_200,
+            profile: "release".into(),
+            timing: Timing {
+                puzzle: PuzzleId::new(year!(2025), day!(1)),
+                parse: None,
+                part_1: Some("10.0µs".into()),
+                part_2: None,
+                total_nanos: 1e+4,
+                timed_out: false,
+                errored: vec![],
+                bench: None,
+            },
+        }
+    }
+
+    #[test]
+    fn round_trips_entries() {
+        for commit in [Some("1a2b3c4"), None] {
+            let entry = get_mock_entry(commit);
+            let line = JsonValue::from(&entry).stringify().unwrap();
+            let parsed = line.parse::<HistoryEntry>().unwrap();
+            assert_eq!(parsed.commit, entry.commit);
+            assert_eq!(parsed.timestamp, entry.timestamp);
+            assert_eq!(parsed.profile, "release");
+            assert_eq!(parsed.timing.part_1, Some("10.0µs".into()));
+        }
+    }
+
+    #[test]
+    fn filters_entries_by_day() {
+        let mut other = get_mock_entry(Some("2"));
+        other.timing.puzzle.day = day!(2);
+        let mut other_year = get_mock_entry(Some("4"));
+        other_year.timing.puzzle.year = year!(2024);
+
+        let content = [
+            get_mock_entry(Some("1")),
+            other,
+            get_mock_entry(Some("3")),
+            other_year,
+        ]
+        .iter()
+        .map(|x| JsonValue::from(x).stringify().unwrap())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+        let entries = parse_entries(&content, PuzzleId::new(year!(2025), day!(1))).unwrap();
+        let commits: Vec<_> = entries.iter().map(|x| x.commit.clone().unwrap()).collect();
+        assert_eq!(commits, vec!["1", "3"]);
+    }
+
+    #[test]
+    fn formats_dates() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(1_733_011_200), "2024-12-01");
+        assert_eq!(format_date(951_782_400), "2000-02-29");
+    }
+}
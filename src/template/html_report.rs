@@ -0,0 +1,444 @@
+/// Module that renders timings as a self-contained HTML report, so benchmark results can be shared in reviews.
+/// Charts are drawn as inline SVG and styles are inlined, the file does not load any external assets.
+use std::fmt::Write;
+use std::time::Duration;
+use std::{fs, io};
+
+use crate::template::compare::{Delta, STEPS, compare};
+use crate::template::history::{current_commit, format_date};
+use crate::template::memory::format_bytes;
+use crate::template::protocol::Step;
+use crate::template::timings::{PartTiming, Timing, Timings};
+
+static REPORT_DIR: &str = "./target";
+static REPORT_FILE_PATH: &str = "./target/benchmarks.html";
+
+/// Colors of the parse step, part 1 and part 2 in all charts.
+static STEP_COLORS: [&str; 3] = ["#8e8e8e", "#4e79a7", "#f28e2b"];
+
+const BAR_CHART_WIDTH: f64 = 600_f64;
+const BOX_PLOT_WIDTH: f64 = 240_f64;
+
+/// Renders a report of the `measured` timings, compared against the `stored` ones, and returns the path it was written to.
+pub fn write(measured: &Timings, stored: &Timings) -> Result<&'static str, io::Error> {
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |x| x.as_secs());
+    let generated = match current_commit() {
+        Some(commit) => format!("{} at commit {commit}", format_date(timestamp)),
+        None => format_date(timestamp),
+    };
+
+    fs::create_dir_all(REPORT_DIR)?;
+    fs::write(REPORT_FILE_PATH, render(measured, stored, &generated))?;
+    Ok(REPORT_FILE_PATH)
+}
+
+fn render(measured: &Timings, stored: &Timings, generated: &str) -> String {
+    let deltas = compare(stored, measured);
+
+    let mut html = String::new();
+
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str("<title>Advent of Code benchmarks</title>\n");
+    html.push_str(STYLE);
+    html.push_str("</head>\n<body>\n<h1>Advent of Code benchmarks</h1>\n");
+
+    let _ = writeln!(
+        html,
+        "<p class=\"muted\">Generated on {} &middot; {} day(s) &middot; total {:.2}ms</p>",
+        escape(generated),
+        measured.data.len(),
+        measured.total_millis()
+    );
+
+    if measured.data.is_empty() {
+        html.push_str("<p>No timings were measured.</p>\n");
+    } else {
+        html.push_str("<h2>Overview</h2>\n");
+        html.push_str(&overview_chart(measured));
+
+        for timing in &measured.data {
+            html.push_str(&day_section(timing, &deltas));
+        }
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// Renders a horizontal bar chart of the median durations of all days, stacked by step.
+fn overview_chart(timings: &Timings) -> String {
+    const LABEL_WIDTH: f64 = 70_f64;
+    const VALUE_WIDTH: f64 = 90_f64;
+    const ROW_HEIGHT: f64 = 24_f64;
+
+    let totals: Vec<Duration> = timings
+        .data
+        .iter()
+        .map(|timing| step_medians(timing).into_iter().flatten().sum())
+        .collect();
+    let max = totals.iter().max().copied().unwrap_or_default();
+
+    #[allow(clippy::cast_precision_loss)]
+    let height = ROW_HEIGHT * (timings.data.len() + 1) as f64;
+    let width = LABEL_WIDTH + BAR_CHART_WIDTH + VALUE_WIDTH;
+
+    let mut svg = format!(
+        "<svg class=\"chart\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\">\n"
+    );
+
+    for (row, (timing, total)) in timings.data.iter().zip(&totals).enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let y = ROW_HEIGHT * row as f64;
+        let mut x = LABEL_WIDTH;
+
+        let _ = writeln!(
+            svg,
+            "<text x=\"0\" y=\"{}\">{}</text>",
+            y + 16_f64,
+            timing.puzzle
+        );
+
+        for ((label, _), (median, color)) in STEPS
+            .iter()
+            .zip(step_medians(timing).into_iter().zip(STEP_COLORS))
+        {
+            let Some(median) = median else { continue };
+            let bar = scale(median, max, BAR_CHART_WIDTH);
+            let _ = writeln!(
+                svg,
+                "<rect x=\"{x:.1}\" y=\"{}\" width=\"{bar:.1}\" height=\"16\" fill=\"{color}\"><title>{label}: {median:.1?}</title></rect>",
+                y + 4_f64
+            );
+            x += bar;
+        }
+
+        let _ = writeln!(
+            svg,
+            "<text x=\"{:.1}\" y=\"{}\">{total:.1?}{}</text>",
+            x + 6_f64,
+            y + 16_f64,
+            if timing.timed_out { " (timed out)" } else { "" }
+        );
+    }
+
+    // legend below the bars.
+    #[allow(clippy::cast_precision_loss)]
+    let y = ROW_HEIGHT * timings.data.len() as f64;
+    for (index, ((label, _), color)) in STEPS.iter().zip(STEP_COLORS).enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let x = LABEL_WIDTH + 90_f64 * index as f64;
+        let _ = writeln!(
+            svg,
+            "<rect x=\"{x}\" y=\"{}\" width=\"12\" height=\"12\" fill=\"{color}\"/><text x=\"{}\" y=\"{}\">{label}</text>",
+            y + 6_f64,
+            x + 16_f64,
+            y + 17_f64
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Renders the breakdown of a single day: one table row per step with its statistics, the comparison with the stored
+/// timing and a plot of the sample distribution.
+fn day_section(timing: &Timing, deltas: &[Delta]) -> String {
+    let mut html = format!(
+        "<h2 id=\"day-{}\">{} Day {}</h2>\n",
+        timing.puzzle, timing.puzzle.year, timing.puzzle.day
+    );
+
+    if timing.timed_out {
+        html.push_str("<p class=\"slower\">This day timed out, its timings are incomplete.</p>\n");
+    }
+
+    if !timing.errored.is_empty() {
+        let steps: Vec<String> = timing
+            .errored
+            .iter()
+            .map(|step| match step {
+                Step::Parse => "parse".into(),
+                Step::Part(part) => format!("part {part}"),
+            })
+            .collect();
+        let _ = writeln!(
+            html,
+            "<p class=\"slower\">Errored steps, not timed: {}.</p>",
+            steps.join(", ")
+        );
+    }
+
+    if let Some(bench) = &timing.bench {
+        let _ = writeln!(
+            html,
+            "<p class=\"muted\">Benched with warmup {}, outliers {}, budget {:?}, {}&ndash;{} samples.</p>",
+            escape(&bench.warmup.to_string()),
+            escape(&bench.outliers.to_string()),
+            bench.budget,
+            bench.min_samples,
+            bench.max_samples
+        );
+    }
+
+    // all distributions of a day share the same axis, so that the steps can be compared visually.
+    let axis_max = STEPS
+        .iter()
+        .filter_map(|(_, get)| get(timing))
+        .filter_map(|part| part.stats.map(|s| s.max).or_else(|| part.median()))
+        .max()
+        .unwrap_or_default();
+
+    html.push_str("<table>\n<tr><th>Step</th><th>Median</th><th>Mean</th><th>&sigma;</th><th>Min</th><th>p95</th><th>Max</th><th>Samples</th><th>Stored</th><th>Distribution</th></tr>\n");
+
+    for (index, (label, get)) in STEPS.iter().enumerate() {
+        let Some(part) = get(timing) else { continue };
+
+        let _ = write!(
+            html,
+            "<tr><td><span class=\"swatch\" style=\"background:{}\"></span>{label}</td>",
+            STEP_COLORS[index]
+        );
+
+        match &part.stats {
+            Some(stats) => {
+                let _ = write!(
+                    html,
+                    "<td><b>{:.1?}</b></td><td>{:.1?}</td><td>{:.1?}</td><td>{:.1?}</td><td>{:.1?}</td><td>{:.1?}</td><td>{}",
+                    stats.median,
+                    stats.mean,
+                    stats.stddev,
+                    stats.min,
+                    stats.p95,
+                    stats.max,
+                    stats.samples
+                );
+                if stats.outliers > 0 {
+                    let _ = write!(
+                        html,
+                        " <span class=\"muted\">(&minus;{})</span>",
+                        stats.outliers
+                    );
+                }
+                html.push_str("</td>");
+            }
+            None => {
+                let _ = write!(
+                    html,
+                    "<td><b>{}</b></td><td colspan=\"6\" class=\"muted\">not benched</td>",
+                    escape(&part.duration)
+                );
+            }
+        }
+
+        let delta = deltas
+            .iter()
+            .find(|x| x.puzzle == timing.puzzle && x.step == *label);
+        let _ = write!(html, "<td>{}</td>", format_delta(delta));
+        let _ = writeln!(html, "<td>{}</td></tr>", box_plot(part, axis_max));
+
+        if let Some(details) = format_details(part) {
+            let _ = writeln!(
+                html,
+                "<tr class=\"details\"><td></td><td colspan=\"9\">{}</td></tr>",
+                escape(&details)
+            );
+        }
+    }
+
+    html.push_str("</table>\n");
+    html
+}
+
+/// Plots the distribution of the kept samples of a step: a whisker from min to max, a box from the first to the third
+/// quartile, a tick for the p95 and a bold tick for the median. Steps that were not benched are drawn as a single dot.
+fn box_plot(part: &PartTiming, axis_max: Duration) -> String {
+    let mut svg = format!(
+        "<svg width=\"{BOX_PLOT_WIDTH}\" height=\"20\" viewBox=\"0 0 {BOX_PLOT_WIDTH} 20\">"
+    );
+
+    match &part.stats {
+        Some(stats) => {
+            let x = |d: Duration| scale(d, axis_max, BOX_PLOT_WIDTH);
+            let box_start = x(stats.q1);
+            let box_end = x(stats.q3);
+
+            let _ = write!(
+                svg,
+                "<title>min {:.1?}, q1 {:.1?}, median {:.1?}, q3 {:.1?}, p95 {:.1?}, max {:.1?}</title>",
+                stats.min, stats.q1, stats.median, stats.q3, stats.p95, stats.max
+            );
+            let _ = write!(
+                svg,
+                "<line x1=\"{:.1}\" x2=\"{:.1}\" y1=\"10\" y2=\"10\" stroke=\"#999\"/>",
+                x(stats.min),
+                x(stats.max)
+            );
+            let _ = write!(
+                svg,
+                "<rect x=\"{box_start:.1}\" y=\"4\" width=\"{:.1}\" height=\"12\" fill=\"#c6d7ea\" stroke=\"#4e79a7\"/>",
+                (box_end - box_start).max(1_f64)
+            );
+            let _ = write!(
+                svg,
+                "<line x1=\"{0:.1}\" x2=\"{0:.1}\" y1=\"4\" y2=\"16\" stroke=\"#999\"/>",
+                x(stats.p95)
+            );
+            let _ = write!(
+                svg,
+                "<line x1=\"{0:.1}\" x2=\"{0:.1}\" y1=\"2\" y2=\"18\" stroke=\"#222\" stroke-width=\"2\"/>",
+                x(stats.median)
+            );
+        }
+        None => {
+            if let Some(median) = part.median() {
+                let _ = write!(
+                    svg,
+                    "<circle cx=\"{:.1}\" cy=\"10\" r=\"3\" fill=\"#222\"/>",
+                    scale(median, axis_max, BOX_PLOT_WIDTH)
+                );
+            }
+        }
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+fn format_delta(delta: Option<&Delta>) -> String {
+    let Some(delta) = delta else {
+        return "<span class=\"muted\">-</span>".into();
+    };
+
+    let percent = delta.percent();
+    let class = if percent > 0_f64 { "slower" } else { "faster" };
+
+    format!(
+        "{:.1?} <span class=\"{class}\">{percent:+.1}%</span>",
+        delta.stored
+    )
+}
+
+/// Memory stats and hardware counters of a step, if any were recorded.
+fn format_details(part: &PartTiming) -> Option<String> {
+    let details: Vec<String> = [
+        part.memory.map(|x| {
+            format!(
+                "peak memory {}, {} in {} allocations",
+                format_bytes(x.peak_bytes),
+                format_bytes(x.allocated_bytes),
+                x.allocations
+            )
+        }),
+        part.counters.map(|x| x.to_string()),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    (!details.is_empty()).then(|| details.join(" · "))
+}
+
+fn step_medians(timing: &Timing) -> [Option<Duration>; 3] {
+    STEPS.map(|(_, get)| get(timing).and_then(PartTiming::median))
+}
+
+/// Scales `value` relative to `max` onto a length of `width` pixels.
+fn scale(value: Duration, max: Duration, width: f64) -> f64 {
+    if max.is_zero() {
+        return 0_f64;
+    }
+    value.as_secs_f64() / max.as_secs_f64() * width
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+static STYLE: &str = "<style>
+body { font-family: system-ui, sans-serif; margin: 2rem auto; max-width: 72rem; padding: 0 1rem; color: #222; }
+h2 { margin-top: 2rem; border-bottom: 1px solid #ddd; }
+table { border-collapse: collapse; font-variant-numeric: tabular-nums; }
+th, td { padding: 0.25rem 0.6rem; text-align: right; vertical-align: middle; }
+th:first-child, td:first-child { text-align: left; }
+tr.details td { text-align: left; font-size: 0.85em; color: #666; padding-top: 0; }
+svg text { font-size: 12px; fill: #222; }
+.muted { color: #888; }
+.slower { color: #c0392b; }
+.faster { color: #27ae60; }
+.swatch { display: inline-block; width: 0.7em; height: 0.7em; margin-right: 0.4em; }
+</style>
+";
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{box_plot, escape, render};
+    use crate::{
+        day,
+        template::{
+            PuzzleId,
+            runner::BenchStats,
+            timings::{PartTiming, Timing, Timings},
+        },
+        year,
+    };
+    use std::time::Duration;
+
+    fn timings(part_1: &str) -> Timings {
+        Timings {
+            data: vec![Timing {
+                puzzle: PuzzleId::new(year!(2025), day!(1)),
+                parse: None,
+                part_1: Some(part_1.into()),
+                part_2: Some("2.0ms".into()),
+                total_nanos: 0_f64,
+                timed_out: false,
+                errored: vec![],
+                bench: None,
+            }],
+        }
+    }
+
+    #[test]
+    fn renders_days_and_comparisons() {
+        let html = render(&timings("12.0µs"), &timings("10.0µs"), "2024-12-01");
+        assert!(html.contains("<h2 id=\"day-2025-01\">2025 Day 01</h2>"));
+        assert!(html.contains("10.0µs <span class=\"slower\">+20.0%</span>"));
+        assert!(html.contains("<svg class=\"chart\""));
+        // the report must not load external assets.
+        assert!(!html.contains("http"));
+    }
+
+    #[test]
+    fn renders_empty_report() {
+        let html = render(&Timings::default(), &Timings::default(), "2024-12-01");
+        assert!(html.contains("No timings were measured."));
+    }
+
+    #[test]
+    fn plots_quartiles_of_samples() {
+        let samples: Vec<Duration> = (1..=8).map(Duration::from_micros).collect();
+        let part = PartTiming {
+            stats: Some(BenchStats::from_samples(&samples)),
+            ..PartTiming::from("4.5µs")
+        };
+
+        // on an axis of 8µs over 240px, q1 = 2µs, median = 4.5µs, q3 = 6µs and p95 = max = 8µs.
+        let svg = box_plot(&part, Duration::from_micros(8));
+        assert!(svg.contains("<line x1=\"30.0\" x2=\"240.0\" y1=\"10\" y2=\"10\""));
+        assert!(svg.contains("<rect x=\"60.0\" y=\"4\" width=\"120.0\""));
+        assert!(svg.contains("<line x1=\"135.0\" x2=\"135.0\" y1=\"2\" y2=\"18\""));
+        assert!(svg.contains("q1 2.0µs, median 4.5µs, q3 6.0µs"));
+    }
+
+    #[test]
+    fn escapes_html() {
+        assert_eq!(escape("<a & \"b\">"), "&lt;a &amp; &quot;b&quot;&gt;");
+    }
+}
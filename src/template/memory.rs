@@ -0,0 +1,188 @@
+/// Lightweight heap profiling for solution steps.
+///
+/// With the `memory-stats` feature, a counting allocator wrapping the system allocator is installed for every binary
+/// that links this crate. It records the peak heap size, the total number of allocated bytes and the number of
+/// allocations, which are reported next to the timing of every step.
+use std::fmt::Display;
+
+#[cfg(all(feature = "memory-stats", feature = "dhat-heap"))]
+compile_error!(
+    "features `memory-stats` and `dhat-heap` both install a global allocator and cannot be combined."
+);
+
+/// Heap usage of a single execution of a solution step.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// Maximum number of bytes that were allocated at the same time, on top of what was allocated before the step.
+    pub peak_bytes: u64,
+    /// Sum of the sizes of all allocations, including memory that was freed again.
+    pub allocated_bytes: u64,
+    pub allocations: u64,
+}
+
+/// Displays the stats, e.g. `peak 1.2 MiB, 3.4 MiB in 120 allocations`.
+impl Display for MemoryStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "peak {}, {} in {} allocations",
+            format_bytes(self.peak_bytes),
+            format_bytes(self.allocated_bytes),
+            self.allocations
+        )
+    }
+}
+
+/// Formats a byte count with a binary unit, e.g. `512 B` or `1.5 KiB`.
+#[must_use]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64 / 1024_f64;
+    let mut unit = 0;
+
+    while value >= 1024_f64 && unit < UNITS.len() - 1 {
+        value /= 1024_f64;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/// Records the heap usage between [`Measurement::start`] and [`Measurement::finish`].
+/// Without the `memory-stats` feature, nothing is recorded.
+pub struct Measurement {
+    #[cfg(feature = "memory-stats")]
+    start: counting::Counters,
+}
+
+impl Measurement {
+    #[must_use]
+    pub fn start() -> Self {
+        Self {
+            #[cfg(feature = "memory-stats")]
+            start: counting::reset_peak(),
+        }
+    }
+
+    /// Returns the heap usage since the measurement started, `None` if the counting allocator is not installed.
+    #[must_use]
+    pub fn finish(self) -> Option<MemoryStats> {
+        #[cfg(feature = "memory-stats")]
+        {
+            let end = counting::Counters::load();
+            Some(MemoryStats {
+                peak_bytes: end.peak.saturating_sub(self.start.current) as u64,
+                allocated_bytes: end.total.saturating_sub(self.start.total) as u64,
+                allocations: end.count.saturating_sub(self.start.count) as u64,
+            })
+        }
+
+        #[cfg(not(feature = "memory-stats"))]
+        None
+    }
+}
+
+#[cfg(feature = "memory-stats")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static CURRENT: AtomicUsize = AtomicUsize::new(0);
+    static PEAK: AtomicUsize = AtomicUsize::new(0);
+    static TOTAL: AtomicUsize = AtomicUsize::new(0);
+    static COUNT: AtomicUsize = AtomicUsize::new(0);
+
+    #[global_allocator]
+    static ALLOC: CountingAllocator = CountingAllocator;
+
+    /// Wraps the system allocator and counts every allocation.
+    struct CountingAllocator;
+
+    fn record_alloc(size: usize) {
+        let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(current, Ordering::Relaxed);
+        TOTAL.fetch_add(size, Ordering::Relaxed);
+        COUNT.fetch_add(1, Ordering::Relaxed);
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = unsafe { System.alloc(layout) };
+            if !ptr.is_null() {
+                record_alloc(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            unsafe { System.dealloc(ptr, layout) };
+            CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+            // a reallocation counts as a new allocation that replaces the old one.
+            if !new_ptr.is_null() {
+                CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+                record_alloc(new_size);
+            }
+            new_ptr
+        }
+    }
+
+    #[derive(Clone, Copy)]
+    pub struct Counters {
+        pub current: usize,
+        pub peak: usize,
+        pub total: usize,
+        pub count: usize,
+    }
+
+    impl Counters {
+        pub fn load() -> Self {
+            Self {
+                current: CURRENT.load(Ordering::Relaxed),
+                peak: PEAK.load(Ordering::Relaxed),
+                total: TOTAL.load(Ordering::Relaxed),
+                count: COUNT.load(Ordering::Relaxed),
+            }
+        }
+    }
+
+    /// Lowers the peak to the current heap size, so that the next peak only reflects what happens afterwards.
+    pub fn reset_peak() -> Counters {
+        PEAK.store(CURRENT.load(Ordering::Relaxed), Ordering::Relaxed);
+        Counters::load()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{MemoryStats, format_bytes};
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn formats_memory_stats() {
+        let stats = MemoryStats {
+            peak_bytes: 2048,
+            allocated_bytes: 4096,
+            allocations: 12,
+        };
+        assert_eq!(stats.to_string(), "peak 2.0 KiB, 4.0 KiB in 12 allocations");
+    }
+}
@@ -1,44 +1,95 @@
+use std::path::{Path, PathBuf};
 use std::{env, fs};
 
 pub mod aoc_cli;
+#[cfg(feature = "native-client")]
+pub mod aoc_client;
+pub mod backend;
+pub mod bench;
 pub mod commands;
+pub mod compare;
+pub mod cpu_time;
+pub mod example_manifest;
+pub mod memory;
+pub mod named_inputs;
+pub mod ocr;
+pub mod outcome;
+pub mod perf;
+pub mod protocol;
+pub mod run_multi;
 pub mod runner;
+pub mod solution;
 
 pub use day::*;
+pub use solution::Solution;
+pub use year::*;
 
+mod answers;
 mod day;
+mod examples;
+mod history;
+mod html_report;
 mod readme_benchmarks;
-mod run_multi;
+mod submissions;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
+pub const ANSI_RED: &str = "\x1b[31m";
+pub const ANSI_GREEN: &str = "\x1b[32m";
+pub const ANSI_YELLOW: &str = "\x1b[33m";
+
+/// The data directory of a year, e.g. `data/2024`. Inputs, examples, puzzles and answers are stored per year.
+#[must_use]
+pub fn data_dir(year: Year) -> PathBuf {
+    Path::new("data").join(year.to_string())
+}
 
 /// Helper function that reads a text file to a string.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, puzzle: PuzzleId) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd
+        .join(data_dir(puzzle.year))
+        .join(folder)
+        .join(format!("{}.txt", puzzle.day));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, puzzle: PuzzleId, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
+        .join(data_dir(puzzle.year))
         .join(folder)
-        .join(format!("{day}-{part}.txt"));
+        .join(format!("{}-{part}.txt", puzzle.day));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `DAY` and `PUZZLE` and sets up the input and runner for each part.
+///
+/// The year of the puzzle is taken from the file name of the solution, `src/bin/<year>-<day>.rs`.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// A day can declare a parse step with `parse = <function>`. The function receives the input and its output is
+/// shared by both parts, which then take a reference to the parsed input. The parse step is timed separately.
+///
+/// ```ignore
+/// advent_of_code::solution!(1, parse = parse_input);
+/// advent_of_code::solution!(1, parse = parse_input, 2);
+/// ```
+///
+/// Instead of free functions, a day can implement the [`Solution`] trait on a type and pass that type:
+///
+/// ```ignore
+/// advent_of_code::solution!(1, Day01);
+/// ```
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -50,19 +101,106 @@ macro_rules! solution {
     ($day:expr, 2) => {
         $crate::solution!(@impl $day, [part_two, 2]);
     };
+    ($day:expr, parse = $parse:expr) => {
+        $crate::solution!(@impl $day, parse = $parse, [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, parse = $parse:expr, 1) => {
+        $crate::solution!(@impl $day, parse = $parse, [part_one, 1]);
+    };
+    ($day:expr, parse = $parse:expr, 2) => {
+        $crate::solution!(@impl $day, parse = $parse, [part_two, 2]);
+    };
+    ($day:expr, $solution:ident) => {
+        $crate::solution!(@header $day);
+
+        /// Runs the solution against the selected input.
+        fn run_input(
+            options: &$crate::template::runner::RunOptions,
+        ) -> Vec<$crate::template::protocol::Report> {
+            let input = options.input.read(PUZZLE);
+            $crate::template::solution::run::<$solution>(PUZZLE, &input, options)
+        }
+
+        /// Solves a part for an example input. Used by the example tests and `cargo solve <day> --examples`.
+        pub fn solve_example(part: u8, input: &str) -> $crate::template::outcome::PartOutcome {
+            $crate::template::solution::solve_example::<$solution>(part, input)
+        }
+    };
 
     (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@header $day);
+
+        /// Runs the solution against the selected input.
+        fn run_input(
+            options: &$crate::template::runner::RunOptions,
+        ) -> Vec<$crate::template::protocol::Report> {
+            use $crate::template::runner::*;
+            let input = options.input.read(PUZZLE);
+            vec![$( run_part($func, &input, PUZZLE, $part, options), )*]
+        }
+
+        /// Solves a part for an example input. Used by the example tests and `cargo solve <day> --examples`.
+        pub fn solve_example(part: u8, input: &str) -> $crate::template::outcome::PartOutcome {
+            use $crate::template::outcome::{PartOutcome, PartResult};
+            match part {
+                $( $part => $func(input).into_outcome(), )*
+                _ => PartOutcome::Unsolved,
+            }
+        }
+    };
+    (@impl $day:expr, parse = $parse:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@header $day);
+
+        /// Runs the solution against the selected input.
+        fn run_input(
+            options: &$crate::template::runner::RunOptions,
+        ) -> Vec<$crate::template::protocol::Report> {
+            use $crate::template::runner::*;
+            let input = options.input.read(PUZZLE);
+            let (parsed, report) = run_parse($parse, input.as_str(), PUZZLE, options);
+            vec![report, $( run_part($func, &parsed, PUZZLE, $part, options), )*]
+        }
+
+        /// Solves a part for an example input. Used by the example tests and `cargo solve <day> --examples`.
+        pub fn solve_example(part: u8, input: &str) -> $crate::template::outcome::PartOutcome {
+            use $crate::template::outcome::{PartOutcome, PartResult};
+            let parsed = $parse(input);
+            match part {
+                $( $part => $func(&parsed).into_outcome(), )*
+                _ => PartOutcome::Unsolved,
+            }
+        }
+    };
+
+    (@header $day:expr) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
+        /// The current puzzle, with the year taken from the file name of the solution.
+        const PUZZLE: $crate::template::PuzzleId =
+            $crate::template::PuzzleId::from_module_path(file!(), DAY);
+
+        /// Runs the solution against the puzzle input and the named inputs of the day. Used by `main` and by the runner
+        /// binary.
+        pub fn run_solution(
+            options: &$crate::template::runner::RunOptions,
+        ) -> $crate::template::runner::RunOutput {
+            let options = &options.for_puzzle(PUZZLE);
+            $crate::template::runner::RunOutput {
+                reports: run_input(options),
+                input_mismatches: $crate::template::named_inputs::run(PUZZLE, options, solve_example),
+            }
+        }
 
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
         fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            if $crate::template::example_manifest::is_requested() {
+                $crate::template::example_manifest::run(PUZZLE, solve_example);
+                return;
+            }
+            run_solution(&$crate::template::runner::RunOptions::from_env_args());
         }
     };
 }
@@ -0,0 +1,358 @@
+/// Additional inputs of a day, e.g. one per account of a team, stored as `data/<year>/inputs/NN/<name>.txt` next to
+/// the puzzle input `data/<year>/inputs/NN.txt`.
+///
+/// After the puzzle input, every named input is solved once and the answers are printed as a table. If present, the
+/// answers are checked against `data/<year>/inputs/NN/answers.json`, which is keyed by input name:
+///
+/// ```json
+/// {
+///   "alice": { "part_1": "142", "part_2": "281" },
+///   "bob": { "part_1": "8" }
+/// }
+/// ```
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::{fs, io, panic};
+
+use tinyjson::JsonValue;
+
+use crate::template::answers::{Verdict, parse_parts};
+use crate::template::example_manifest::SolveExample;
+use crate::template::outcome::PartOutcome;
+use crate::template::protocol::InputMismatch;
+use crate::template::runner::RunOptions;
+use crate::template::{ANSI_BOLD, ANSI_RED, ANSI_RESET, PuzzleId, data_dir, ocr};
+
+static ANSWERS_FILE_NAME: &str = "answers.json";
+
+/// A file in the inputs directory of a day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NamedInput {
+    /// The file name without extension, e.g. `alice` for `alice.txt`.
+    pub name: String,
+    pub path: PathBuf,
+}
+
+/// Known answers of the named inputs of a day, indexed by input name and part.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InputAnswers {
+    data: BTreeMap<String, [Option<String>; 2]>,
+}
+
+impl InputAnswers {
+    /// Reads the answers file of a directory. If not present, returns no answers.
+    pub fn read(dir: &Path) -> Result<Self, String> {
+        match fs::read_to_string(dir.join(ANSWERS_FILE_NAME)) {
+            Ok(content) => content.parse(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    #[must_use]
+    pub fn check(&self, name: &str, part: u8, answer: Option<&str>) -> Verdict {
+        let expected = self
+            .data
+            .get(name)
+            .and_then(|answers| answers.get(usize::from(part).checked_sub(1)?))
+            .and_then(Option::as_deref);
+
+        match (expected, answer) {
+            (None, _) => Verdict::Unknown,
+            (Some(expected), Some(answer)) if expected == answer => Verdict::Match,
+            (Some(expected), _) => Verdict::Mismatch {
+                expected: expected.into(),
+            },
+        }
+    }
+}
+
+/// The inputs directory of a puzzle, e.g. `data/2024/inputs/01`.
+#[must_use]
+pub fn dir(puzzle: PuzzleId) -> PathBuf {
+    data_dir(puzzle.year)
+        .join("inputs")
+        .join(puzzle.day.to_string())
+}
+
+/// Lists the `.txt` files of a directory, sorted by name. A missing directory yields no inputs.
+pub fn list(dir: &Path) -> Result<Vec<NamedInput>, String> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e.to_string()),
+    };
+
+    let mut inputs = vec![];
+
+    for entry in entries {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if path.extension().is_some_and(|x| x == "txt")
+            && let Some(name) = path.file_stem().and_then(|x| x.to_str())
+        {
+            inputs.push(NamedInput {
+                name: name.into(),
+                path,
+            });
+        }
+    }
+
+    inputs.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(inputs)
+}
+
+/// Solves every named input of a day, prints the answer table and returns the answers that do not match the answers
+/// file. Does nothing when benching or when the day is run against another input than the puzzle input, see
+/// [`RunOptions`].
+pub fn run(puzzle: PuzzleId, options: &RunOptions, solve: SolveExample) -> Vec<InputMismatch> {
+    if options.time.is_some() || !options.input.is_puzzle() {
+        return vec![];
+    }
+
+    let dir = dir(puzzle);
+
+    let inputs = list(&dir).unwrap_or_else(|e| {
+        eprintln!("Failed to list {}: {e}", dir.display());
+        vec![]
+    });
+    if inputs.is_empty() {
+        return vec![];
+    }
+
+    let answers = InputAnswers::read(&dir).unwrap_or_else(|e| {
+        eprintln!(
+            "Failed to read {}, answers are not checked: {e}",
+            dir.join(ANSWERS_FILE_NAME).display()
+        );
+        InputAnswers::default()
+    });
+
+    let mut rows: Vec<[String; 3]> = vec![["Input".into(), "Part 1".into(), "Part 2".into()]];
+    let mut mismatches = vec![];
+
+    for input in &inputs {
+        let content = match fs::read_to_string(&input.path) {
+            Ok(content) => content,
+            Err(e) => {
+                let error = format!("✖ {e}");
+                rows.push([input.name.clone(), error, String::new()]);
+                continue;
+            }
+        };
+
+        let [part_one, part_two] = [1, 2].map(|part| {
+            let outcome = solve_part(solve, part, &content);
+            let answer = outcome.answer().map(ocr::read);
+            let verdict = answers.check(&input.name, part, answer.as_deref());
+            if matches!(verdict, Verdict::Mismatch { .. }) {
+                let mismatch = InputMismatch {
+                    puzzle,
+                    input: input.name.clone(),
+                    part,
+                };
+                mismatch.emit();
+                mismatches.push(mismatch);
+            }
+            format_cell(&outcome, answer.as_deref(), &verdict)
+        });
+
+        rows.push([input.name.clone(), part_one, part_two]);
+    }
+
+    println!();
+    print_table(&rows);
+
+    if !mismatches.is_empty() {
+        println!(
+            "{ANSI_RED}{} answer(s) do not match {}.{ANSI_RESET}",
+            mismatches.len(),
+            dir.join(ANSWERS_FILE_NAME).display()
+        );
+    }
+
+    mismatches
+}
+
+/// Solves a part of a named input. A panic, e.g. an `unwrap` that fails on someone else's input, is turned into an
+/// errored outcome so that it does not discard the reports of the puzzle input. Its message has already been printed
+/// by the panic hook.
+fn solve_part(solve: SolveExample, part: u8, input: &str) -> PartOutcome {
+    panic::catch_unwind(|| solve(part, input)).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("unknown cause");
+        PartOutcome::Errored(format!("panicked: {message}"))
+    })
+}
+
+/// Formats the outcome of a part as a table cell. Cells are plain text so that the columns line up.
+fn format_cell(outcome: &PartOutcome, answer: Option<&str>, verdict: &Verdict) -> String {
+    let verdict = match verdict {
+        Verdict::Match => "✔".into(),
+        Verdict::Mismatch { expected } if expected.contains('\n') => {
+            "✘ expected a different answer".into()
+        }
+        Verdict::Mismatch { expected } => format!("✘ expected {expected}"),
+        Verdict::Unknown => "?".into(),
+    };
+
+    match (outcome, answer) {
+        // drawings that are not letters do not fit in a cell.
+        (PartOutcome::Solved(_), Some(answer)) if answer.contains('\n') => format!("▼ {verdict}"),
+        (PartOutcome::Solved(_), Some(answer)) => format!("{answer} {verdict}"),
+        (PartOutcome::Errored(error), _) => {
+            format!("✖ {}", error.lines().next().unwrap_or_default())
+        }
+        _ => "✖".into(),
+    }
+}
+
+fn print_table(rows: &[[String; 3]]) {
+    let widths: Vec<usize> = (0..3)
+        .map(|col| {
+            rows.iter()
+                .map(|row| row[col].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    for (index, row) in rows.iter().enumerate() {
+        let line: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect();
+
+        if index == 0 {
+            println!("{ANSI_BOLD}{}{ANSI_RESET}", line.join("  ").trim_end());
+        } else {
+            println!("{}", line.join("  ").trim_end());
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl std::str::FromStr for InputAnswers {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = s
+            .parse::<JsonValue>()
+            .or(Err("answers file is not valid JSON."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answers to be a JSON object.")?;
+
+        let data = json
+            .iter()
+            .map(|(name, value)| Ok((name.clone(), parse_parts(name, value)?)))
+            .collect::<Result<_, String>>()?;
+
+        Ok(InputAnswers { data })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{InputAnswers, NamedInput, format_cell, list, solve_part};
+    use crate::template::answers::Verdict;
+    use crate::template::outcome::PartOutcome;
+    use std::{env, fs};
+
+    #[test]
+    fn checks_answers_by_input_name() {
+        let answers: InputAnswers =
+            r#"{"alice":{"part_1":"142","part_2":"281"},"bob":{"part_1":"8"}}"#
+                .parse()
+                .unwrap();
+
+        assert_eq!(answers.check("alice", 1, Some("142")), Verdict::Match);
+        assert_eq!(answers.check("alice", 2, Some("281")), Verdict::Match);
+        assert_eq!(
+            answers.check("bob", 1, None),
+            Verdict::Mismatch {
+                expected: "8".into()
+            }
+        );
+        assert_eq!(answers.check("bob", 2, Some("1")), Verdict::Unknown);
+        assert_eq!(answers.check("carol", 1, Some("1")), Verdict::Unknown);
+
+        assert!("[]".parse::<InputAnswers>().is_err());
+    }
+
+    #[test]
+    fn lists_text_files_by_name() {
+        let dir = env::temp_dir().join(format!("aoc-named-inputs-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for file in ["bob.txt", "alice.txt", "answers.json"] {
+            fs::write(dir.join(file), "").unwrap();
+        }
+
+        let inputs = list(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            inputs.unwrap(),
+            vec![
+                NamedInput {
+                    name: "alice".into(),
+                    path: dir.join("alice.txt"),
+                },
+                NamedInput {
+                    name: "bob".into(),
+                    path: dir.join("bob.txt"),
+                },
+            ]
+        );
+        assert_eq!(list(&dir.join("missing")), Ok(vec![]));
+    }
+
+    #[test]
+    fn catches_panics_of_parts() {
+        let solve = |part: u8, input: &str| match part {
+            1 => PartOutcome::Solved(input.len().to_string()),
+            _ => panic!("no solution for {input}"),
+        };
+
+        assert_eq!(solve_part(solve, 1, "abc"), PartOutcome::Solved("3".into()));
+        assert_eq!(
+            solve_part(solve, 2, "abc"),
+            PartOutcome::Errored("panicked: no solution for abc".into())
+        );
+    }
+
+    #[test]
+    fn formats_cells() {
+        let solved = PartOutcome::Solved("142".into());
+        assert_eq!(format_cell(&solved, Some("142"), &Verdict::Match), "142 ✔");
+        assert_eq!(
+            format_cell(
+                &solved,
+                Some("142"),
+                &Verdict::Mismatch {
+                    expected: "143".into()
+                }
+            ),
+            "142 ✘ expected 143"
+        );
+        assert_eq!(
+            format_cell(
+                &PartOutcome::Errored("invalid grid\ncaused by: x".into()),
+                None,
+                &Verdict::Unknown
+            ),
+            "✖ invalid grid"
+        );
+        assert_eq!(
+            format_cell(&PartOutcome::Unsolved, None, &Verdict::Unknown),
+            "✖"
+        );
+    }
+}
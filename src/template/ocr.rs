@@ -0,0 +1,207 @@
+/// Recognizes answers that are drawn as capital letters, as in the puzzles where a screen or a sheet of paper has to be
+/// rendered to read the answer.
+///
+/// Advent of Code uses two fonts: letters that are 6 pixels high (most years) and letters that are 10 pixels high
+/// (2018). Lit pixels are drawn as `#` or `█`, any other character is dark:
+///
+/// ```text
+/// #..#.####.###.
+/// #..#.#....#..#
+/// ####.###..#..#   →   "HEP"
+/// #..#.#....###.
+/// #..#.#....#...
+/// #..#.####.#...
+/// ```
+use std::borrow::Cow;
+use std::mem;
+
+/// Letters that are 6 pixels high, usually 4 pixels wide.
+static SMALL_FONT: [(char, [&str; 6]); 18] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', ["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// Letters that are 10 pixels high and 6 pixels wide.
+#[rustfmt::skip]
+static LARGE_FONT: [(char, [&str; 10]); 15] = [
+    ('A', ["..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#"]),
+    ('B', ["#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#", "#....#", "#####."]),
+    ('C', [".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#....#", ".####."]),
+    ('E', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('F', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('G', [".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+    ('H', ["#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#", "#....#"]),
+    ('J', ["...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+    ('K', ["#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+    ('L', ["#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('N', ["#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+    ('P', ["#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('R', ["#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+    ('X', ["#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+    ('Z', ["######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....", "#.....", "######"]),
+];
+
+/// Decodes an answer drawn in one of the Advent of Code fonts. Returns `None` if the answer is not a drawing of
+/// letters, or if it contains a letter that is not known.
+#[must_use]
+pub fn decode(answer: &str) -> Option<String> {
+    let rows: Vec<Vec<bool>> = answer
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.chars().map(is_lit).collect())
+        .collect();
+
+    let width = rows.iter().map(Vec::len).max()?;
+    let column = |x: usize| -> Vec<bool> {
+        rows.iter()
+            .map(|row| row.get(x).copied().unwrap_or(false))
+            .collect()
+    };
+
+    // letters are separated by at least one dark column.
+    let mut glyphs: Vec<Vec<Vec<bool>>> = vec![];
+    let mut current: Vec<Vec<bool>> = vec![];
+
+    for x in 0..width {
+        let column = column(x);
+        if column.iter().any(|x| *x) {
+            current.push(column);
+        } else if !current.is_empty() {
+            glyphs.push(mem::take(&mut current));
+        }
+    }
+    if !current.is_empty() {
+        glyphs.push(current);
+    }
+
+    if glyphs.is_empty() {
+        return None;
+    }
+
+    glyphs
+        .iter()
+        .map(|columns| match rows.len() {
+            6 => recognize(columns, &SMALL_FONT),
+            10 => recognize(columns, &LARGE_FONT),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The text form of an answer: multi-line answers that are drawn as letters are decoded, any other answer is returned
+/// as is. This is the form that is checked, recorded and submitted.
+#[must_use]
+pub fn read(answer: &str) -> Cow<'_, str> {
+    if answer.contains('\n')
+        && let Some(decoded) = decode(answer)
+    {
+        Cow::Owned(decoded)
+    } else {
+        Cow::Borrowed(answer)
+    }
+}
+
+fn is_lit(c: char) -> bool {
+    matches!(c, '#' | '█')
+}
+
+/// Finds the letter of a font whose lit pixels match the columns of a glyph.
+fn recognize<const N: usize>(columns: &[Vec<bool>], font: &[(char, [&str; N])]) -> Option<char> {
+    font.iter()
+        .find(|(_, rows)| {
+            let width = rows.iter().map(|row| row.len()).max().unwrap_or_default();
+            width == columns.len()
+                && columns.iter().enumerate().all(|(x, column)| {
+                    rows.iter()
+                        .zip(column)
+                        .all(|(row, lit)| row.chars().nth(x).is_some_and(is_lit) == *lit)
+                })
+        })
+        .map(|(letter, _)| *letter)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{LARGE_FONT, SMALL_FONT, decode, read};
+
+    #[test]
+    fn decodes_small_letters() {
+        let answer = [
+            "#..#.####.###..####.###....##..##..#...",
+            "#..#.#....#..#....#.#..#....#.#..#.#...",
+            "####.###..#..#...#..#..#....#.#....#...",
+            "#..#.#....###...#...###.....#.#.##.#...",
+            "#..#.#....#....#....#....#..#.#..#.#...",
+            "#..#.####.#....####.#.....##...###.####",
+        ]
+        .join("\n");
+        assert_eq!(decode(&answer), Some("HEPZPJGL".into()));
+    }
+
+    #[test]
+    fn decodes_every_letter_of_both_fonts() {
+        let small: Vec<String> = (0..6)
+            .map(|y| {
+                SMALL_FONT
+                    .iter()
+                    .map(|(_, rows)| rows[y])
+                    .collect::<Vec<_>>()
+                    .join(".")
+            })
+            .collect();
+        let letters: String = SMALL_FONT.iter().map(|(letter, _)| letter).collect();
+        assert_eq!(decode(&small.join("\n")), Some(letters));
+
+        let large: Vec<String> = (0..10)
+            .map(|y| {
+                LARGE_FONT
+                    .iter()
+                    .map(|(_, rows)| rows[y])
+                    .collect::<Vec<_>>()
+                    .join("..")
+            })
+            .collect();
+        let letters: String = LARGE_FONT.iter().map(|(letter, _)| letter).collect();
+        assert_eq!(decode(&large.join("\n")), Some(letters));
+    }
+
+    #[test]
+    fn accepts_blocks_and_surrounding_blank_lines() {
+        let answer = "\n█  █\n█  █\n████\n█  █\n█  █\n█  █\n\n";
+        assert_eq!(decode(answer), Some("H".into()));
+    }
+
+    #[test]
+    fn rejects_drawings_that_are_not_letters() {
+        assert_eq!(decode("42"), None);
+        assert_eq!(decode(""), None);
+        assert_eq!(decode("#..#\n#..#\n####\n#..#"), None);
+        // an unknown glyph.
+        assert_eq!(decode("#\n#\n#\n#\n#\n#"), None);
+    }
+
+    #[test]
+    fn reads_only_multi_line_answers() {
+        assert_eq!(read("#..#\n#..#\n####\n#..#\n#..#\n#..#"), "H");
+        assert_eq!(read("#"), "#");
+        assert_eq!(read("1\n2"), "1\n2");
+    }
+}
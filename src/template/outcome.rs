@@ -0,0 +1,158 @@
+/// Return types accepted for the parts of a solution.
+///
+/// A part either returns an `Option`, where `None` means that the part is not solved yet, or a `Result`, whose error
+/// is printed with its chain of sources instead of panicking. Any error that converts into `Box<dyn Error>` works,
+/// including `Box<dyn Error>` itself and `String`:
+///
+/// ```ignore
+/// pub fn part_one(input: &str) -> Result<u64, Box<dyn Error>> {
+///     let grid = Grid::parse(input)?;
+///     Ok(grid.count_paths())
+/// }
+/// ```
+use std::error::Error;
+use std::fmt::Display;
+
+/// The outcome of a part, see [`PartResult`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PartOutcome {
+    Solved(String),
+    /// The part returned `None`.
+    Unsolved,
+    /// The part returned an error, rendered with its sources, see [`render_error`].
+    Errored(String),
+}
+
+impl PartOutcome {
+    #[must_use]
+    pub fn answer(&self) -> Option<&str> {
+        match self {
+            PartOutcome::Solved(answer) => Some(answer),
+            PartOutcome::Unsolved | PartOutcome::Errored(_) => None,
+        }
+    }
+
+    #[must_use]
+    pub fn error(&self) -> Option<&str> {
+        match self {
+            PartOutcome::Errored(error) => Some(error),
+            PartOutcome::Solved(_) | PartOutcome::Unsolved => None,
+        }
+    }
+}
+
+/// Implemented for the return types a part may have: `Option<T>` and `Result<T, E>`.
+pub trait PartResult {
+    /// The answer, if the part is solved. Used to print the result before the part is benched.
+    fn answer(&self) -> Option<String>;
+
+    fn into_outcome(self) -> PartOutcome;
+}
+
+impl<T: Display> PartResult for Option<T> {
+    fn answer(&self) -> Option<String> {
+        self.as_ref().map(ToString::to_string)
+    }
+
+    fn into_outcome(self) -> PartOutcome {
+        match self {
+            Some(answer) => PartOutcome::Solved(answer.to_string()),
+            None => PartOutcome::Unsolved,
+        }
+    }
+}
+
+impl<T: Display, E: Into<Box<dyn Error>>> PartResult for Result<T, E> {
+    fn answer(&self) -> Option<String> {
+        self.as_ref().ok().map(ToString::to_string)
+    }
+
+    fn into_outcome(self) -> PartOutcome {
+        match self {
+            Ok(answer) => PartOutcome::Solved(answer.to_string()),
+            Err(e) => PartOutcome::Errored(render_error(e.into().as_ref())),
+        }
+    }
+}
+
+/// Renders an error followed by one `caused by` line per source, e.g.:
+///
+/// ```text
+/// invalid grid
+/// caused by: unexpected character 'x' at line 3
+/// ```
+#[must_use]
+pub fn render_error(error: &dyn Error) -> String {
+    let mut rendered = error.to_string();
+    let mut source = error.source();
+
+    while let Some(e) = source {
+        rendered.push_str(&format!("\ncaused by: {e}"));
+        source = e.source();
+    }
+
+    rendered
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{PartOutcome, PartResult};
+    use std::{error::Error, fmt::Display, num::ParseIntError};
+
+    #[derive(Debug)]
+    struct GridError(ParseIntError);
+
+    impl Display for GridError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("invalid grid")
+        }
+    }
+
+    impl Error for GridError {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    #[test]
+    fn converts_options() {
+        assert_eq!(Some(42).into_outcome(), PartOutcome::Solved("42".into()));
+        assert_eq!(None::<u64>.into_outcome(), PartOutcome::Unsolved);
+    }
+
+    #[test]
+    fn renders_error_chains() {
+        let result: Result<u64, GridError> = "x".parse::<u64>().map_err(GridError);
+        assert_eq!(result.answer(), None);
+
+        let outcome = result.into_outcome();
+        assert_eq!(
+            outcome,
+            PartOutcome::Errored("invalid grid\ncaused by: invalid digit found in string".into())
+        );
+        assert_eq!(outcome.answer(), None);
+
+        let result: Result<u64, GridError> = Ok(7);
+        assert_eq!(result.into_outcome().answer(), Some("7"));
+    }
+
+    #[test]
+    fn accepts_boxed_and_string_errors() {
+        let result: Result<u64, Box<dyn Error>> =
+            Err(Box::new(GridError("".parse::<u64>().unwrap_err())));
+        assert_eq!(
+            result.into_outcome(),
+            PartOutcome::Errored(
+                "invalid grid\ncaused by: cannot parse integer from empty string".into()
+            )
+        );
+
+        let result: Result<u64, String> = Err("no path found".into());
+        assert_eq!(
+            result.into_outcome(),
+            PartOutcome::Errored("no path found".into())
+        );
+    }
+}
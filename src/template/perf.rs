@@ -0,0 +1,242 @@
+/// Hardware performance counters for benchmarks, read via `perf_event_open(2)` on Linux.
+///
+/// Counters are far less noisy than wall-clock time on shared machines. They are opted into with `--counters` and
+/// fall back gracefully: on other platforms, or if the kernel refuses access (see `/proc/sys/kernel/perf_event_paranoid`),
+/// a warning is printed once and the benchmark continues without them.
+use std::fmt::Display;
+
+/// Counts of hardware events, averaged per execution of a step. A counter is `None` if the CPU does not support it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PerfCounters {
+    pub cycles: Option<u64>,
+    pub instructions: Option<u64>,
+    pub branch_misses: Option<u64>,
+    pub cache_misses: Option<u64>,
+}
+
+/// Displays the available counters, e.g. `1.2M cycles, 3.4M instructions, 1.2k branch misses, 300 cache misses`.
+impl Display for PerfCounters {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let counters: Vec<String> = [
+            (self.cycles, "cycles"),
+            (self.instructions, "instructions"),
+            (self.branch_misses, "branch misses"),
+            (self.cache_misses, "cache misses"),
+        ]
+        .into_iter()
+        .filter_map(|(value, label)| value.map(|x| format!("{} {label}", format_count(x))))
+        .collect();
+
+        f.write_str(&counters.join(", "))
+    }
+}
+
+/// Formats a count with a metric suffix, e.g. `950`, `1.2k` or `3.4M`.
+#[must_use]
+pub fn format_count(count: u64) -> String {
+    #[allow(clippy::cast_precision_loss)]
+    let value = count as f64;
+
+    match count {
+        0..1_000 => count.to_string(),
+        1_000..1_000_000 => format!("{:.1}k", value / 1e3),
+        1_000_000..1_000_000_000 => format!("{:.1}M", value / 1e6),
+        _ => format!("{:.1}G", value / 1e9),
+    }
+}
+
+/// A set of counters that measure the current thread while enabled.
+pub struct Counters {
+    #[cfg(target_os = "linux")]
+    events: [Option<linux::Event>; 4],
+}
+
+impl Counters {
+    /// Opens all counters that are available. Returns an error if none are.
+    pub fn open() -> Result<Self, String> {
+        #[cfg(target_os = "linux")]
+        {
+            use linux::{Event, HW_BRANCH_MISSES, HW_CACHE_MISSES, HW_CPU_CYCLES, HW_INSTRUCTIONS};
+
+            let mut error = None;
+            let events = [
+                HW_CPU_CYCLES,
+                HW_INSTRUCTIONS,
+                HW_BRANCH_MISSES,
+                HW_CACHE_MISSES,
+            ]
+            .map(|config| Event::open(config).map_err(|e| error = Some(e)).ok());
+
+            if events.iter().all(Option::is_none) {
+                return Err(error.map_or_else(|| "unknown error".into(), |e| e.to_string()));
+            }
+
+            Ok(Self { events })
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        Err("hardware counters are only supported on Linux".into())
+    }
+
+    /// Resets and enables all counters.
+    pub fn start(&self) {
+        #[cfg(target_os = "linux")]
+        for event in self.events.iter().flatten() {
+            event.start();
+        }
+    }
+
+    /// Disables all counters and returns their values divided by `iterations`.
+    pub fn stop(&self, iterations: u64) -> PerfCounters {
+        #[cfg(target_os = "linux")]
+        {
+            let [cycles, instructions, branch_misses, cache_misses] = self
+                .events
+                .each_ref()
+                .map(|event| event.as_ref().and_then(linux::Event::stop))
+                .map(|value| value.map(|x| x / iterations.max(1)));
+
+            PerfCounters {
+                cycles,
+                instructions,
+                branch_misses,
+                cache_misses,
+            }
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = iterations;
+            PerfCounters::default()
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::io;
+
+    pub const HW_CPU_CYCLES: u64 = 0;
+    pub const HW_INSTRUCTIONS: u64 = 1;
+    pub const HW_CACHE_MISSES: u64 = 3;
+    pub const HW_BRANCH_MISSES: u64 = 5;
+
+    const PERF_TYPE_HARDWARE: u32 = 0;
+    const PERF_FLAG_FD_CLOEXEC: libc::c_ulong = 1 << 3;
+
+    // ioctl requests are cast at the call sites, `libc::ioctl` takes a `c_ulong` on glibc but a `c_int` on musl.
+    const PERF_EVENT_IOC_ENABLE: libc::c_ulong = 0x2400;
+    const PERF_EVENT_IOC_DISABLE: libc::c_ulong = 0x2401;
+    const PERF_EVENT_IOC_RESET: libc::c_ulong = 0x2403;
+
+    // bits of `PerfEventAttr::flags`.
+    const DISABLED: u64 = 1 << 0;
+    const EXCLUDE_KERNEL: u64 = 1 << 5;
+    const EXCLUDE_HV: u64 = 1 << 6;
+
+    /// The first version of `struct perf_event_attr` (`PERF_ATTR_SIZE_VER0`), which every kernel accepts.
+    #[repr(C)]
+    #[derive(Default)]
+    struct PerfEventAttr {
+        type_: u32,
+        size: u32,
+        config: u64,
+        sample_period: u64,
+        sample_type: u64,
+        read_format: u64,
+        flags: u64,
+        wakeup_events: u32,
+        bp_type: u32,
+        config1: u64,
+    }
+
+    /// A single hardware counter of the calling thread.
+    pub struct Event {
+        fd: libc::c_int,
+    }
+
+    impl Event {
+        pub fn open(config: u64) -> io::Result<Self> {
+            let attr = PerfEventAttr {
+                type_: PERF_TYPE_HARDWARE,
+                size: u32::try_from(size_of::<PerfEventAttr>()).unwrap(),
+                config,
+                flags: DISABLED | EXCLUDE_KERNEL | EXCLUDE_HV,
+                ..PerfEventAttr::default()
+            };
+
+            // measure the calling thread (pid 0) on any cpu (-1), without a group leader (-1).
+            let fd = unsafe {
+                libc::syscall(
+                    libc::SYS_perf_event_open,
+                    &raw const attr,
+                    0,
+                    -1,
+                    -1,
+                    PERF_FLAG_FD_CLOEXEC,
+                )
+            };
+
+            if fd < 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            Ok(Self {
+                fd: libc::c_int::try_from(fd).map_err(io::Error::other)?,
+            })
+        }
+
+        pub fn start(&self) {
+            unsafe {
+                libc::ioctl(self.fd, PERF_EVENT_IOC_RESET as _, 0);
+                libc::ioctl(self.fd, PERF_EVENT_IOC_ENABLE as _, 0);
+            }
+        }
+
+        pub fn stop(&self) -> Option<u64> {
+            let mut value: u64 = 0;
+
+            let read = unsafe {
+                libc::ioctl(self.fd, PERF_EVENT_IOC_DISABLE as _, 0);
+                libc::read(self.fd, (&raw mut value).cast(), size_of::<u64>())
+            };
+
+            (read == 8).then_some(value)
+        }
+    }
+
+    impl Drop for Event {
+        fn drop(&mut self) {
+            unsafe { libc::close(self.fd) };
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{PerfCounters, format_count};
+
+    #[test]
+    fn formats_counts() {
+        assert_eq!(format_count(950), "950");
+        assert_eq!(format_count(1_200), "1.2k");
+        assert_eq!(format_count(3_400_000), "3.4M");
+        assert_eq!(format_count(5_000_000_000), "5.0G");
+    }
+
+    #[test]
+    fn formats_available_counters() {
+        let counters = PerfCounters {
+            cycles: Some(1_200_000),
+            instructions: Some(3_400_000),
+            branch_misses: None,
+            cache_misses: Some(300),
+        };
+        assert_eq!(
+            counters.to_string(),
+            "1.2M cycles, 3.4M instructions, 300 cache misses"
+        );
+    }
+}
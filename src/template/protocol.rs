@@ -0,0 +1,600 @@
+/// Machine-readable protocol between solution binaries and `run_multi`.
+///
+/// When the environment variable [`REPORT_FILE_ENV`] is set, the runner appends one JSON object per line to the
+/// file it points to for every executed step, e.g.:
+///
+/// ```json
+/// {"year":"2024","day":"01","part":"1","answer":"42","nanos":74130,"samples":100,"stats":{...}}
+/// ```
+///
+/// Before a step is executed, a start event is written, which allows to tell which step a killed process was stuck in:
+///
+/// ```json
+/// {"event":"start","year":"2024","day":"01","part":"1"}
+/// ```
+///
+/// Once the first execution of a step is done and it is benched (`--time`), a bench event is written. Warmup and
+/// sampling are bounded by the bench budget, so they do not count towards the timeout of `run_multi`:
+///
+/// ```json
+/// {"event":"bench","year":"2024","day":"01","part":"1"}
+/// ```
+///
+/// A named input whose answer does not match its answers file is reported as:
+///
+/// ```json
+/// {"event":"input_mismatch","year":"2024","day":"01","input":"alice","part":"1"}
+/// ```
+///
+/// The pretty console output is unaffected, so solutions are free to print whatever they like.
+use std::collections::HashMap;
+use std::env;
+use std::fmt::Display;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use tinyjson::JsonValue;
+
+use crate::template::{
+    PuzzleId, bench::BenchConfig, memory::MemoryStats, perf::PerfCounters, runner::BenchStats,
+};
+
+/// Environment variable holding the path of the file that reports are appended to.
+pub const REPORT_FILE_ENV: &str = "AOC_REPORT_FILE";
+
+/// An executed step of a solution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Step {
+    Parse,
+    Part(u8),
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Step::Parse => f.write_str("parse"),
+            Step::Part(part) => write!(f, "{part}"),
+        }
+    }
+}
+
+impl FromStr for Step {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Step::Parse),
+            s => s
+                .parse()
+                .map(Step::Part)
+                .map_err(|_| format!("unknown step \"{s}\".")),
+        }
+    }
+}
+
+/// The outcome of a single step, as reported by a solution binary.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Report {
+    pub puzzle: PuzzleId,
+    pub step: Step,
+    /// The answer, `None` for the parse step or if the part is not solved.
+    pub answer: Option<String>,
+    /// The rendered error if the step failed, see [`PartOutcome`](crate::template::outcome::PartOutcome).
+    pub error: Option<String>,
+    pub stats: BenchStats,
+    /// Heap usage of the step, only recorded with the `memory-stats` feature.
+    pub memory: Option<MemoryStats>,
+    /// Hardware counters per execution, only recorded when benched with `--counters`.
+    pub counters: Option<PerfCounters>,
+    /// The settings the step was benched with, `None` if it was executed only once.
+    pub bench: Option<BenchConfig>,
+}
+
+/// A named input whose answer does not match the answers file of its directory, see
+/// [`named_inputs`](crate::template::named_inputs).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InputMismatch {
+    pub puzzle: PuzzleId,
+    /// The file name of the input without extension, e.g. `alice`.
+    pub input: String,
+    pub part: u8,
+}
+
+impl InputMismatch {
+    /// Appends the mismatch to the file set in [`REPORT_FILE_ENV`]. Does nothing if the variable is not set.
+    pub fn emit(&self) {
+        append_line(&JsonValue::from(&Event::InputMismatch(self.clone())));
+    }
+}
+
+impl Report {
+    /// Appends the report to the file set in [`REPORT_FILE_ENV`]. Does nothing if the variable is not set.
+    pub fn emit(&self) {
+        append_line(&JsonValue::from(self));
+    }
+
+    /// Appends a start event for a step to the file set in [`REPORT_FILE_ENV`].
+    pub fn emit_started(puzzle: PuzzleId, step: Step) {
+        append_line(&JsonValue::from(&Event::Started { puzzle, step }));
+    }
+
+    /// Appends a bench event for a step to the file set in [`REPORT_FILE_ENV`].
+    pub fn emit_benching(puzzle: PuzzleId, step: Step) {
+        append_line(&JsonValue::from(&Event::Benching { puzzle, step }));
+    }
+}
+
+/// A line of a report file.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Event {
+    Started {
+        puzzle: PuzzleId,
+        step: Step,
+    },
+    /// The first execution of a step finished and the step is being benched.
+    Benching {
+        puzzle: PuzzleId,
+        step: Step,
+    },
+    Finished(Box<Report>),
+    InputMismatch(InputMismatch),
+}
+
+/// Reads all events from a report file. A missing file yields no events.
+pub fn read_events(path: &Path) -> Result<Vec<Event>, String> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e.to_string()),
+    };
+
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(Event::from_str)
+        .collect()
+}
+
+/// Returns the step that was started last without finishing, i.e. the step a killed process was stuck in.
+pub fn unfinished_step(events: &[Event]) -> Option<Step> {
+    events
+        .iter()
+        .rev()
+        .find_map(|event| match event {
+            Event::Started { step, .. } | Event::Benching { step, .. } => Some(Some(*step)),
+            Event::Finished(_) => Some(None),
+            Event::InputMismatch(_) => None,
+        })
+        .flatten()
+}
+
+fn append_line(json: &JsonValue) {
+    let Ok(path) = env::var(REPORT_FILE_ENV) else {
+        return;
+    };
+
+    let result = json.stringify().map_err(io::Error::other).and_then(|line| {
+        let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+        writeln!(file, "{line}")
+    });
+
+    if let Err(e) = result {
+        eprintln!("Failed to write report to \"{path}\": {e}");
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Report> for JsonValue {
+    fn from(value: &Report) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        insert_puzzle(&mut map, value.puzzle);
+        map.insert("part".into(), JsonValue::String(value.step.to_string()));
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "error".into(),
+            match &value.error {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+
+        #[allow(clippy::cast_precision_loss)]
+        {
+            map.insert(
+                "nanos".into(),
+                JsonValue::Number(value.stats.median.as_nanos() as f64),
+            );
+            map.insert(
+                "samples".into(),
+                JsonValue::Number(value.stats.samples as f64),
+            );
+        }
+
+        map.insert("stats".into(), JsonValue::from(&value.stats));
+        map.insert(
+            "memory".into(),
+            match &value.memory {
+                Some(x) => JsonValue::from(x),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "counters".into(),
+            match &value.counters {
+                Some(x) => JsonValue::from(x),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "bench".into(),
+            match &value.bench {
+                Some(x) => JsonValue::from(x),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl From<&Event> for JsonValue {
+    fn from(value: &Event) -> Self {
+        match value {
+            Event::Finished(report) => JsonValue::from(report.as_ref()),
+            Event::Started { puzzle, step } | Event::Benching { puzzle, step } => {
+                let kind = match value {
+                    Event::Started { .. } => "start",
+                    _ => "bench",
+                };
+                let mut map: HashMap<String, JsonValue> = HashMap::new();
+                map.insert("event".into(), JsonValue::String(kind.into()));
+                insert_puzzle(&mut map, *puzzle);
+                map.insert("part".into(), JsonValue::String(step.to_string()));
+                JsonValue::Object(map)
+            }
+            Event::InputMismatch(mismatch) => {
+                let mut map: HashMap<String, JsonValue> = HashMap::new();
+                map.insert("event".into(), JsonValue::String("input_mismatch".into()));
+                insert_puzzle(&mut map, mismatch.puzzle);
+                map.insert("input".into(), JsonValue::String(mismatch.input.clone()));
+                map.insert("part".into(), JsonValue::String(mismatch.part.to_string()));
+                JsonValue::Object(map)
+            }
+        }
+    }
+}
+
+impl FromStr for Event {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = JsonValue::from_str(s).or(Err("report is not valid JSON."))?;
+
+        let map = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected report to be a JSON object.")?;
+
+        let part = || {
+            map.get("part")
+                .and_then(|v| v.get::<String>())
+                .ok_or("Expected event.part to be a string.")
+        };
+
+        match map
+            .get("event")
+            .and_then(|v| v.get::<String>())
+            .map(String::as_str)
+        {
+            Some("start") => Ok(Event::Started {
+                puzzle: parse_puzzle(map, "event")?,
+                step: part()?.parse()?,
+            }),
+            Some("bench") => Ok(Event::Benching {
+                puzzle: parse_puzzle(map, "event")?,
+                step: part()?.parse()?,
+            }),
+            Some("input_mismatch") => Ok(Event::InputMismatch(InputMismatch {
+                puzzle: parse_puzzle(map, "event")?,
+                input: map
+                    .get("input")
+                    .and_then(|v| v.get::<String>())
+                    .ok_or("Expected event.input to be a string.")?
+                    .clone(),
+                part: part()?
+                    .parse()
+                    .map_err(|_| "Expected event.part to be a number.")?,
+            })),
+            _ => Report::from_str(s).map(|report| Event::Finished(Box::new(report))),
+        }
+    }
+}
+
+impl FromStr for Report {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = JsonValue::from_str(s).or(Err("report is not valid JSON."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected report to be a JSON object.")?;
+
+        let puzzle = parse_puzzle(json, "report")?;
+
+        let step = json
+            .get("part")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected report.part to be a string.")?
+            .parse()?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected report.answer to be null or string.")?
+            .cloned();
+
+        let error = match json.get("error") {
+            None | Some(JsonValue::Null) => None,
+            Some(JsonValue::String(x)) => Some(x.clone()),
+            Some(_) => return Err("Expected report.error to be null or string.".into()),
+        };
+
+        let stats = BenchStats::try_from(
+            json.get("stats")
+                .ok_or("Expected report to have key `stats`.")?,
+        )?;
+
+        let memory = match json.get("memory") {
+            None | Some(JsonValue::Null) => None,
+            Some(v) => Some(MemoryStats::try_from(v)?),
+        };
+
+        let counters = match json.get("counters") {
+            None | Some(JsonValue::Null) => None,
+            Some(v) => Some(PerfCounters::try_from(v)?),
+        };
+
+        let bench = match json.get("bench") {
+            None | Some(JsonValue::Null) => None,
+            Some(v) => Some(BenchConfig::try_from(v)?),
+        };
+
+        Ok(Report {
+            puzzle,
+            step,
+            answer,
+            error,
+            stats,
+            memory,
+            counters,
+            bench,
+        })
+    }
+}
+
+fn insert_puzzle(map: &mut HashMap<String, JsonValue>, puzzle: PuzzleId) {
+    map.insert("year".into(), JsonValue::String(puzzle.year.to_string()));
+    map.insert("day".into(), JsonValue::String(puzzle.day.to_string()));
+}
+
+fn parse_puzzle(map: &HashMap<String, JsonValue>, name: &str) -> Result<PuzzleId, String> {
+    let year = map
+        .get("year")
+        .and_then(|v| v.get::<String>())
+        .and_then(|year| year.parse().ok())
+        .ok_or(format!("Expected {name}.year to be a Year struct."))?;
+
+    let day = map
+        .get("day")
+        .and_then(|v| v.get::<String>())
+        .and_then(|day| day.parse().ok())
+        .ok_or(format!("Expected {name}.day to be a Day struct."))?;
+
+    Ok(PuzzleId::new(year, day))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Event, InputMismatch, Report, Step, unfinished_step};
+    use crate::{
+        day,
+        template::{
+            PuzzleId, bench::BenchConfig, memory::MemoryStats, perf::PerfCounters,
+            runner::BenchStats,
+        },
+        year,
+    };
+    use std::time::Duration;
+    use tinyjson::JsonValue;
+
+    fn get_mock_report() -> Report {
+        Report {
+            puzzle: PuzzleId::new(year!(2024), day!(7)),
+            step: Step::Part(1),
+            answer: Some("42".into()),
+            error: None,
+            stats: BenchStats::from_samples(&[Duration::from_micros(3), Duration::from_micros(5)]),
+            memory: None,
+            counters: None,
+            bench: Some(BenchConfig::default()),
+        }
+    }
+
+    #[test]
+    fn round_trips_reports() {
+        let report = get_mock_report();
+        let line = JsonValue::from(&report).stringify().unwrap();
+        assert_eq!(line.parse::<Report>().unwrap(), report);
+    }
+
+    #[test]
+    fn round_trips_memory_stats() {
+        let report = Report {
+            memory: Some(MemoryStats {
+                peak_bytes: 1024,
+                allocated_bytes: 4096,
+                allocations: 3,
+            }),
+            ..get_mock_report()
+        };
+        let line = JsonValue::from(&report).stringify().unwrap();
+        assert_eq!(line.parse::<Report>().unwrap(), report);
+    }
+
+    #[test]
+    fn round_trips_perf_counters() {
+        let report = Report {
+            counters: Some(PerfCounters {
+                cycles: Some(1200),
+                instructions: Some(3400),
+                branch_misses: None,
+                cache_misses: Some(5),
+            }),
+            ..get_mock_report()
+        };
+        let line = JsonValue::from(&report).stringify().unwrap();
+        assert_eq!(line.parse::<Report>().unwrap(), report);
+    }
+
+    #[test]
+    fn round_trips_parse_step() {
+        let report = Report {
+            step: Step::Parse,
+            answer: None,
+            ..get_mock_report()
+        };
+        let line = JsonValue::from(&report).stringify().unwrap();
+        assert_eq!(line.parse::<Report>().unwrap(), report);
+    }
+
+    #[test]
+    fn round_trips_errors() {
+        let report = Report {
+            answer: None,
+            error: Some("invalid grid\ncaused by: unexpected character".into()),
+            ..get_mock_report()
+        };
+        let line = JsonValue::from(&report).stringify().unwrap();
+        assert_eq!(line.parse::<Report>().unwrap(), report);
+    }
+
+    #[test]
+    fn parses_report_lines() {
+        let line = r#"{"year":"2024","day":"03","part":"2","answer":null,"nanos":1500,"samples":1,"stats":{"samples":1,"mean_nanos":1500,"median_nanos":1500,"stddev_nanos":0,"min_nanos":1500,"max_nanos":1500,"p95_nanos":1500}}"#;
+        let report = line.parse::<Report>().unwrap();
+        assert_eq!(report.puzzle, PuzzleId::new(year!(2024), day!(3)));
+        assert_eq!(report.step, Step::Part(2));
+        assert_eq!(report.answer, None);
+        assert_eq!(report.stats.median, Duration::from_nanos(1500));
+    }
+
+    #[test]
+    fn parses_start_events() {
+        let line = r#"{"event":"start","year":"2024","day":"05","part":"parse"}"#;
+        assert_eq!(
+            line.parse::<Event>().unwrap(),
+            Event::Started {
+                puzzle: PuzzleId::new(year!(2024), day!(5)),
+                step: Step::Parse
+            }
+        );
+
+        let event = Event::Benching {
+            puzzle: PuzzleId::new(year!(2024), day!(5)),
+            step: Step::Part(1),
+        };
+        let line = JsonValue::from(&event).stringify().unwrap();
+        assert_eq!(line.parse::<Event>().unwrap(), event);
+
+        let line = JsonValue::from(&Event::Finished(Box::new(get_mock_report())))
+            .stringify()
+            .unwrap();
+        assert_eq!(
+            line.parse::<Event>().unwrap(),
+            Event::Finished(Box::new(get_mock_report()))
+        );
+    }
+
+    #[test]
+    fn round_trips_input_mismatches() {
+        let event = Event::InputMismatch(InputMismatch {
+            puzzle: PuzzleId::new(year!(2024), day!(5)),
+            input: "alice".into(),
+            part: 2,
+        });
+        let line = JsonValue::from(&event).stringify().unwrap();
+        assert_eq!(line.parse::<Event>().unwrap(), event);
+
+        let line = r#"{"event":"input_mismatch","year":"2024","day":"05","part":"2"}"#;
+        assert!(line.parse::<Event>().is_err());
+    }
+
+    #[test]
+    fn finds_unfinished_step() {
+        let started = |step| Event::Started {
+            puzzle: PuzzleId::new(year!(2024), day!(7)),
+            step,
+        };
+
+        assert_eq!(unfinished_step(&[]), None);
+        assert_eq!(
+            unfinished_step(&[started(Step::Part(1))]),
+            Some(Step::Part(1))
+        );
+        assert_eq!(
+            unfinished_step(&[
+                started(Step::Part(1)),
+                Event::Finished(Box::new(get_mock_report())),
+                started(Step::Part(2)),
+            ]),
+            Some(Step::Part(2))
+        );
+        assert_eq!(
+            unfinished_step(&[
+                started(Step::Part(1)),
+                Event::Finished(Box::new(get_mock_report()))
+            ]),
+            None
+        );
+        assert_eq!(
+            unfinished_step(&[
+                started(Step::Part(2)),
+                Event::Benching {
+                    puzzle: PuzzleId::new(year!(2024), day!(7)),
+                    step: Step::Part(2),
+                },
+            ]),
+            Some(Step::Part(2))
+        );
+        assert_eq!(
+            unfinished_step(&[
+                Event::Finished(Box::new(get_mock_report())),
+                Event::InputMismatch(InputMismatch {
+                    puzzle: PuzzleId::new(year!(2024), day!(7)),
+                    input: "alice".into(),
+                    part: 1,
+                }),
+            ]),
+            None
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_reports() {
+        assert!("Part 1: 42 (1ms)".parse::<Report>().is_err());
+        assert!(r#"{"day":"01"}"#.parse::<Report>().is_err());
+        assert!(r#"{"event":"start","day":"01","part":"1"}"#.parse::<Event>().is_err());
+    }
+}
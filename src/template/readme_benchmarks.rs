@@ -2,8 +2,10 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
-use crate::template::Day;
-use crate::template::timings::Timings;
+use crate::template::PuzzleId;
+use crate::template::memory::format_bytes;
+use crate::template::protocol::Step;
+use crate::template::timings::{PartTiming, Timing, Timings};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -26,8 +28,8 @@ pub struct TablePosition {
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(puzzle: PuzzleId) -> String {
+    format!("./src/bin/{puzzle}.rs")
 }
 
 fn locate_table(readme: &str) -> Result<TablePosition, Error> {
@@ -55,22 +57,57 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
 fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
     let header = format!("{prefix} Benchmarks");
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    // only show the parse column if at least one day declares a parse step.
+    let has_parse = timings.data.iter().any(|t| t.parse.is_some());
+    // only show the memory column if at least one day was benched with the `memory-stats` feature.
+    let has_memory = timings.data.iter().any(|t| peak_memory(t).is_some());
+
+    let mut lines: Vec<String> = vec![MARKER.into(), header, String::new()];
+
+    match (has_parse, has_memory) {
+        (true, true) => {
+            lines.push("| Day | Parse | Part 1 | Part 2 | Peak memory |".into());
+            lines.push("| :---: | :---: | :---: | :---: | :---:  |".into());
+        }
+        (true, false) => {
+            lines.push("| Day | Parse | Part 1 | Part 2 |".into());
+            lines.push("| :---: | :---: | :---: | :---:  |".into());
+        }
+        (false, true) => {
+            lines.push("| Day | Part 1 | Part 2 | Peak memory |".into());
+            lines.push("| :---: | :---: | :---: | :---:  |".into());
+        }
+        (false, false) => {
+            lines.push("| Day | Part 1 | Part 2 |".into());
+            lines.push("| :---: | :---: | :---:  |".into());
+        }
+    }
 
     for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
+        let path = get_path_for_bin(timing.puzzle);
+        let parse = if has_parse {
+            format!(" {} |", format_step(&timing, Step::Parse))
+        } else {
+            String::new()
+        };
+        let memory = if has_memory {
+            match peak_memory(&timing) {
+                Some(bytes) => format!(" `{}` |", format_bytes(bytes)),
+                None => " `-` |".into(),
+            }
+        } else {
+            String::new()
+        };
+
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
+            "| [{} Day {}]({}) |{} {} | {} |{}",
+            timing.puzzle.year,
+            timing.puzzle.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            parse,
+            format_step(&timing, Step::Part(1)),
+            format_step(&timing, Step::Part(2)),
+            memory
         ));
     }
 
@@ -81,6 +118,42 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
     lines.join("\n")
 }
 
+/// Formats a step of a day, see [`format_cell`]. Errored steps are marked as such.
+fn format_step(timing: &Timing, step: Step) -> String {
+    if timing.errored.contains(&step) {
+        return "`✖ errored`".into();
+    }
+
+    format_cell(match step {
+        Step::Parse => timing.parse.as_ref(),
+        Step::Part(1) => timing.part_1.as_ref(),
+        Step::Part(2) => timing.part_2.as_ref(),
+        Step::Part(_) => None,
+    })
+}
+
+/// Formats a part as its headline duration, followed by the extended statistics if benched.
+fn format_cell(part: Option<&PartTiming>) -> String {
+    match part {
+        Some(PartTiming {
+            duration,
+            stats: Some(stats),
+            ..
+        }) => format!("`{duration}` <sub>{stats}</sub>"),
+        Some(PartTiming { duration, .. }) => format!("`{duration}`"),
+        None => "`-`".into(),
+    }
+}
+
+/// The highest peak heap usage of all steps of a day.
+fn peak_memory(timing: &Timing) -> Option<u64> {
+    [&timing.parse, &timing.part_1, &timing.part_2]
+        .into_iter()
+        .filter_map(|part| part.as_ref().and_then(|x| x.memory))
+        .map(|memory| memory.peak_bytes)
+        .max()
+}
+
 fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
     let positions = locate_table(s)?;
     let table = construct_table("##", timings, total_millis);
@@ -100,28 +173,49 @@ pub fn update(timings: Timings) -> Result<(), Error> {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{MARKER, update_content};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use crate::{
+        day,
+        template::PuzzleId,
+        template::memory::MemoryStats,
+        template::protocol::Step,
+        template::runner::BenchStats,
+        template::timings::{PartTiming, Timing, Timings},
+        year,
+    };
+    use std::time::Duration;
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
+                    puzzle: PuzzleId::new(year!(2025), day!(1)),
+                    parse: None,
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    timed_out: false,
+                    errored: vec![],
+                    bench: None,
                 },
                 Timing {
-                    day: day!(2),
+                    puzzle: PuzzleId::new(year!(2025), day!(2)),
+                    parse: None,
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    timed_out: false,
+                    errored: vec![],
+                    bench: None,
                 },
                 Timing {
-                    day: day!(4),
+                    puzzle: PuzzleId::new(year!(2025), day!(4)),
+                    parse: None,
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
                     total_nanos: 9e+10,
+                    timed_out: false,
+                    errored: vec![],
+                    bench: None,
                 },
             ],
         }
@@ -169,9 +263,9 @@ mod tests {
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [2025 Day 1](./src/bin/2025-01.rs) | `10ms` | `20ms` |",
+            "| [2025 Day 2](./src/bin/2025-02.rs) | `30ms` | `40ms` |",
+            "| [2025 Day 4](./src/bin/2025-04.rs) | `40ms` | `50ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +274,83 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_statistics() {
+        let stats = BenchStats {
+            samples: 100,
+            outliers: 0,
+            mean: Duration::from_micros(12),
+            median: Duration::from_micros(10),
+            stddev: Duration::from_micros(1),
+            min: Duration::from_micros(9),
+            q1: Duration::from_micros(9),
+            q3: Duration::from_micros(11),
+            max: Duration::from_micros(30),
+            p95: Duration::from_micros(15),
+        };
+
+        let timings = Timings {
+            data: vec![Timing {
+                puzzle: PuzzleId::new(year!(2025), day!(1)),
+                parse: None,
+                part_1: Some(PartTiming {
+                    duration: "10.0µs".into(),
+                    stats: Some(stats),
+                    memory: None,
+                    counters: None,
+                }),
+                part_2: None,
+                total_nanos: 1e+4,
+                timed_out: false,
+                errored: vec![],
+                bench: None,
+            }],
+        };
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 0.01).unwrap();
+        assert!(s.contains(
+            "| [2025 Day 1](./src/bin/2025-01.rs) | `10.0µs` <sub>mean 12.0µs, σ 1.0µs, min 9.0µs, max 30.0µs, p95 15.0µs</sub> | `-` |"
+        ));
+    }
+
+    #[test]
+    fn format_benchmarks_with_parse_step() {
+        let mut timings = get_mock_timings();
+        timings.data[0].parse = Some("5ms".into());
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0).unwrap();
+        assert!(s.contains("| Day | Parse | Part 1 | Part 2 |"));
+        assert!(s.contains("| [2025 Day 1](./src/bin/2025-01.rs) | `5ms` | `10ms` | `20ms` |"));
+        assert!(s.contains("| [2025 Day 2](./src/bin/2025-02.rs) | `-` | `30ms` | `40ms` |"));
+    }
+
+    #[test]
+    fn format_benchmarks_with_errored_steps() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_2 = None;
+        timings.data[0].errored = vec![Step::Part(2)];
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0).unwrap();
+        assert!(s.contains("| [2025 Day 1](./src/bin/2025-01.rs) | `10ms` | `✖ errored` |"));
+    }
+
+    #[test]
+    fn format_benchmarks_with_memory() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_2.as_mut().unwrap().memory = Some(MemoryStats {
+            peak_bytes: 2048,
+            allocated_bytes: 4096,
+            allocations: 3,
+        });
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0).unwrap();
+        assert!(s.contains("| Day | Part 1 | Part 2 | Peak memory |"));
+        assert!(s.contains("| [2025 Day 1](./src/bin/2025-01.rs) | `10ms` | `20ms` | `2.0 KiB` |"));
+        assert!(s.contains("| [2025 Day 2](./src/bin/2025-02.rs) | `30ms` | `40ms` | `-` |"));
+    }
 }
@@ -1,56 +1,398 @@
-use std::{collections::HashSet, io};
-
-use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    env,
+    fmt::Display,
+    io,
+    panic::{self, AssertUnwindSafe},
+    process::{self, Command},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
 
-use super::{
-    all_days,
-    timings::{Timing, Timings},
+use crate::template::{
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RED, ANSI_RESET, PuzzleId, Year,
+    answers::{Answers, Verdict},
+    bench::BenchConfig,
+    cpu_time,
+    protocol::{InputMismatch, Report, Step},
+    runner::{InputSource, RunOptions, RunOutput, Solution},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+use super::timings::{Timing, Timings};
+
+/// Settings shared by all days executed by [`run_multi`].
+#[derive(Clone, Copy, Debug)]
+pub struct RunMultiOptions {
+    pub is_release: bool,
+    /// Whether days are benched and their timings collected.
+    pub is_timed: bool,
+    pub bench: BenchConfig,
+    /// Number of days that are executed concurrently.
+    pub jobs: usize,
+    /// Maximum wall time of a single step. A day exceeding it is killed and the remaining days continue.
+    /// Days can override it in the `bench.json` of their year, see [`timeout_override`](super::bench::timeout_override).
+    pub timeout: Option<Duration>,
+}
+
+/// Outcome of [`run_multi`].
+#[derive(Clone, Debug, Default)]
+pub struct RunSummary {
+    /// Timings of all days, only collected if `options.is_timed` is set.
+    pub timings: Option<Timings>,
+    /// Parts whose answer does not match the answers store, as `(puzzle, part)`.
+    pub mismatches: Vec<(PuzzleId, u8)>,
+    /// Steps that returned an error instead of an answer.
+    pub errors: Vec<(PuzzleId, Step)>,
+    /// Named inputs whose answers do not match, see [`named_inputs`](crate::template::named_inputs).
+    pub input_mismatches: Vec<InputMismatch>,
+}
+
+impl RunSummary {
+    /// Whether any answer was wrong or any step errored.
+    pub fn has_failures(&self) -> bool {
+        !self.mismatches.is_empty() || !self.errors.is_empty() || !self.input_mismatches.is_empty()
+    }
+}
+
+/// Outcome of running a single day.
+pub struct DayRun {
+    /// Reports of the executed steps, `None` if the day has not been scaffolded yet.
+    pub reports: Option<Vec<Report>>,
+    /// Named inputs whose answers do not match.
+    pub input_mismatches: Vec<InputMismatch>,
+    /// Set if the day was killed because a step exceeded the timeout.
+    pub timed_out: Option<TimedOut>,
+    /// Console output of the day if it was buffered instead of printed directly.
+    pub stdout: String,
+    pub stderr: String,
+    /// CPU time used by the day, `None` if it cannot be measured on this platform.
+    pub cpu_time: Option<Duration>,
+}
+
+/// A day that was killed because one of its steps exceeded the timeout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimedOut {
+    /// The step that was running, `None` if the day was killed before its first step started.
+    pub step: Option<Step>,
+    pub after: Duration,
+}
 
-    let mut need_space = false;
+impl Display for TimedOut {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.step {
+            Some(Step::Parse) => f.write_str("Parse")?,
+            Some(Step::Part(part)) => write!(f, "Part {part}")?,
+            None => f.write_str("Startup")?,
+        }
+        write!(f, ": ✖ timed out after {:?}", self.after)
+    }
+}
+
+/// Runs the given puzzles in order, checks their answers against the answers store of their year and collects their
+/// timings if `options.is_timed` is set.
+///
+/// Days are executed in-process if `solutions` were compiled into this binary, see [`needs_profile_switch`].
+/// Otherwise, every day is built and executed as its own binary.
+///
+/// With `jobs > 1`, days are executed concurrently as child processes. Their output is buffered and printed in day order.
+/// With a timeout, days are always executed as child processes so that a hanging solution can be killed.
+pub fn run_multi(
+    puzzles_to_run: &HashSet<PuzzleId>,
+    options: &RunMultiOptions,
+    solutions: &[Solution],
+) -> RunSummary {
+    // NOTE: use non-duplicate, sorted puzzles.
+    let mut days: Vec<PuzzleId> = puzzles_to_run.iter().copied().collect();
+    days.sort_unstable();
+
+    let mut answers: HashMap<Year, Answers> = HashMap::new();
+
+    let mut timings: Vec<Timing> = Vec::with_capacity(days.len());
+    let mut mismatches: Vec<(PuzzleId, u8)> = vec![];
+    let mut input_mismatches: Vec<InputMismatch> = vec![];
+    let mut errors: Vec<(PuzzleId, Step)> = vec![];
+    let mut timed_out_days: Vec<(PuzzleId, TimedOut)> = vec![];
+    let mut cpu_time: Option<Duration> = None;
+    let wall_timer = Instant::now();
+
+    let print_header = |index: usize, day: PuzzleId| {
+        if index > 0 {
+            println!();
+        }
+        println!("{ANSI_BOLD}{}{ANSI_RESET}", label(day));
+        println!("------");
+    };
+
+    let mut handle_run = |day: PuzzleId, run: DayRun| {
+        print!("{}", run.stdout);
+        eprint!("{}", run.stderr);
+
+        if let Some(timed_out) = run.timed_out {
+            print!("\r");
+            println!("{timed_out}             ");
+            timed_out_days.push((day, timed_out));
+        }
+
+        match run.reports {
+            Some(reports) => {
+                let answers = answers.entry(day.year).or_insert_with(|| {
+                    Answers::read_from_file(day.year).unwrap_or_else(|e| {
+                        eprintln!(
+                            "Failed to read the answers store of {}, answers are not checked: {e}",
+                            day.year
+                        );
+                        Answers::default()
+                    })
+                });
+
+                for report in &reports {
+                    if let (Step::Part(part), Some(answer)) = (report.step, &report.answer)
+                        && let Verdict::Mismatch { .. } = answers.check(day.day, part, answer)
+                    {
+                        mismatches.push((day, part));
+                    }
+                }
 
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
+                let mut timing = child_commands::timing_from_reports(&reports, day);
+                timing.timed_out = run.timed_out.is_some();
+                errors.extend(timing.errored.iter().map(|step| (day, *step)));
+                timings.push(timing);
             }
-            need_space = true;
+            None => println!("Not solved."),
+        }
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+        input_mismatches.extend(run.input_mismatches);
+        if let Some(day_cpu_time) = run.cpu_time {
+            cpu_time = Some(cpu_time.unwrap_or_default() + day_cpu_time);
+        }
+    };
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    if options.jobs > 1 {
+        run_parallel(
+            &days,
+            options.jobs,
+            |day| child_commands::run_day(day, options, solutions, true),
+            |index, day, run| {
+                print_header(index, day);
+                handle_run(day, run);
+            },
+        );
+    } else {
+        for (index, day) in days.iter().copied().enumerate() {
+            print_header(index, day);
 
-            if output.is_empty() {
-                println!("Not solved.");
+            let run = if solutions.is_empty() || options.timeout.is_some() {
+                child_commands::run_day(day, options, solutions, false)
             } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
-            }
-        });
+                let run_options = RunOptions {
+                    time: options.is_timed.then_some(options.bench),
+                    submit: None,
+                    input: InputSource::Puzzle,
+                };
+
+                let cpu_before = cpu_time::of_self();
+                let (reports, input_mismatches) = match run_in_process(solutions, day, &run_options)
+                {
+                    Some(output) => (Some(output.reports), output.input_mismatches),
+                    None => (None, vec![]),
+                };
+
+                DayRun {
+                    reports,
+                    input_mismatches,
+                    timed_out: None,
+                    stdout: String::new(),
+                    stderr: String::new(),
+                    cpu_time: cpu_time::of_self()
+                        .zip(cpu_before)
+                        .map(|(after, before)| after.saturating_sub(before)),
+                }
+            };
 
-    if is_timed {
+            handle_run(day, run);
+        }
+    }
+
+    if options.jobs > 1 {
+        let wall_time = wall_timer.elapsed();
+        let jobs = options.jobs;
+        print!(
+            "\n{ANSI_BOLD}Total (Wall):{ANSI_RESET} {ANSI_ITALIC}{wall_time:.2?}{ANSI_RESET} with {jobs} jobs"
+        );
+        match cpu_time {
+            Some(cpu_time) => println!(
+                ", {ANSI_BOLD}Total (CPU):{ANSI_RESET} {ANSI_ITALIC}{cpu_time:.2?}{ANSI_RESET}"
+            ),
+            None => println!(),
+        }
+    }
+
+    if !timed_out_days.is_empty() {
+        let summary: Vec<String> = timed_out_days
+            .iter()
+            .map(|(day, timed_out)| match timed_out.step {
+                Some(Step::Parse) => format!("{} (parse)", label(*day)),
+                Some(Step::Part(part)) => format!("{} (part {part})", label(*day)),
+                None => label(*day),
+            })
+            .collect();
+
+        println!("\n{ANSI_BOLD}Timed out:{ANSI_RESET} {}", summary.join(", "));
+    }
+
+    if !errors.is_empty() {
+        let summary: Vec<String> = errors
+            .iter()
+            .map(|(day, step)| match step {
+                Step::Parse => format!("{} (parse)", label(*day)),
+                Step::Part(part) => format!("{} (part {part})", label(*day)),
+            })
+            .collect();
+
+        println!(
+            "\n{ANSI_RED}{ANSI_BOLD}Errored:{ANSI_RESET} {}",
+            summary.join(", ")
+        );
+    }
+
+    if !mismatches.is_empty() || !input_mismatches.is_empty() {
+        let summary: Vec<String> = mismatches
+            .iter()
+            .map(|(day, part)| format!("{} (part {part})", label(*day)))
+            .chain(
+                input_mismatches
+                    .iter()
+                    .map(|x| format!("{} ({}, part {})", label(x.puzzle), x.input, x.part)),
+            )
+            .collect();
+
+        println!(
+            "\n{ANSI_RED}{ANSI_BOLD}Wrong answers:{ANSI_RESET} {}",
+            summary.join(", ")
+        );
+    }
+
+    let timings = options.is_timed.then(|| {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
+        timings
+    });
+
+    RunSummary {
+        timings,
+        mismatches,
+        errors,
+        input_mismatches,
+    }
+}
+
+/// How a puzzle is referred to in headers and summaries, e.g. `2024 Day 01`.
+fn label(puzzle: PuzzleId) -> String {
+    format!("{} Day {}", puzzle.year, puzzle.day)
+}
+
+/// Runs `run` for every day on up to `jobs` threads and passes the results to `on_done` in day order.
+fn run_parallel(
+    days: &[PuzzleId],
+    jobs: usize,
+    run: impl Fn(PuzzleId) -> DayRun + Sync,
+    mut on_done: impl FnMut(usize, PuzzleId, DayRun),
+) {
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            let tx = tx.clone();
+            let (next, run) = (&next, &run);
+
+            scope.spawn(move || {
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(day) = days.get(index) else {
+                        break;
+                    };
+                    if tx.send((index, run(*day))).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
+        drop(tx);
+
+        // buffer results that finish early until all preceding days have been printed.
+        let mut finished: BTreeMap<usize, DayRun> = BTreeMap::new();
+        let mut next_to_print = 0;
+
+        for (index, run) in rx {
+            finished.insert(index, run);
+
+            while let Some(run) = finished.remove(&next_to_print) {
+                on_done(next_to_print, days[next_to_print], run);
+                next_to_print += 1;
+            }
+        }
+    });
+}
+
+/// Runs a solution compiled into this binary. Returns `None` if the day has not been scaffolded yet.
+fn run_in_process(
+    solutions: &[Solution],
+    puzzle: PuzzleId,
+    options: &RunOptions,
+) -> Option<RunOutput> {
+    let solution = solutions.iter().find(|s| s.puzzle == puzzle)?;
+
+    // a panicking day (e.g. due to a missing input file) should not abort the remaining days.
+    // its message has already been printed by the panic hook.
+    Some(panic::catch_unwind(AssertUnwindSafe(|| (solution.run)(options))).unwrap_or_default())
+}
+
+/// Whether the solutions compiled into this binary were built with a different profile than requested.
+pub fn needs_profile_switch(solutions: &[Solution], is_release: bool) -> bool {
+    !solutions.is_empty() && is_release == cfg!(debug_assertions)
+}
+
+/// Re-runs the current command with a runner binary built in the requested profile, then exits with its status.
+pub fn switch_profile(is_release: bool) -> ! {
+    let mut args: Vec<String> = vec!["run".into(), "--quiet".into()];
+
+    if is_release {
+        args.push("--release".into());
+    }
+
+    args.extend(feature_args());
+    args.push("--".into());
+    args.extend(env::args().skip(1));
+
+    let status = Command::new("cargo").args(&args).status();
+    process::exit(status.ok().and_then(|s| s.code()).unwrap_or(1));
+}
+
+/// Cargo arguments that enable the features of this binary which affect how solutions are measured.
+fn feature_args() -> Vec<String> {
+    if cfg!(feature = "memory-stats") {
+        vec!["--features".into(), "memory-stats".into()]
     } else {
-        None
+        vec![]
     }
 }
 
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
-    BrokenPipe,
     IO(io::Error),
+    Protocol(String),
+    /// The binary of a day failed to compile, holds the compiler output.
+    Build(String),
 }
 
 impl From<std::io::Error> for Error {
@@ -60,198 +402,493 @@ impl From<std::io::Error> for Error {
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(puzzle: PuzzleId) -> String {
+    format!("./src/bin/{puzzle}.rs")
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting their reports.
 pub mod child_commands {
-    use super::{Error, get_path_for_bin};
-    use crate::template::Day;
+    use super::{DayRun, Error, RunMultiOptions, TimedOut, feature_args, get_path_for_bin};
+    use crate::template::{
+        PuzzleId,
+        bench::timeout_override,
+        cpu_time::{self, Exited},
+        protocol::{Event, REPORT_FILE_ENV, Report, Step, read_events, unfinished_step},
+        runner::Solution,
+        timings::{PartTiming, Timing},
+    };
     use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
+        collections::HashMap,
+        env, fs,
+        io::{self, Read},
+        path::{Path, PathBuf},
+        process::{Child, Command, Stdio},
         thread,
+        time::{Duration, Instant},
     };
+    use tinyjson::JsonValue;
+
+    /// Builds the command that executes a day in a child process.
+    /// If `solutions` were compiled into this binary, the day runs in a copy of it. Otherwise, the binary of the day is
+    /// built first and spawned directly, since killing `cargo run` on timeout would leave the solution running.
+    fn build_command(
+        day: PuzzleId,
+        options: &RunMultiOptions,
+        solutions: &[Solution],
+    ) -> Result<Command, Error> {
+        let mut cmd = if solutions.is_empty() {
+            Command::new(build_bin(day, options.is_release)?)
+        } else {
+            let mut cmd = Command::new(env::current_exe()?);
+            cmd.args(["run-day", &day.to_string()]);
+            cmd
+        };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+        if options.is_timed {
+            // mirror `--time` flag and bench settings to child invocations.
+            cmd.arg("--time").args(options.bench.to_args());
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        Ok(cmd)
+    }
 
+    /// Compiles the binary of a day with `cargo build` and returns the path of its executable.
+    fn build_bin(day: PuzzleId, is_release: bool) -> Result<PathBuf, Error> {
+        let mut cmd = Command::new("cargo");
+        cmd.args([
+            "build",
+            "--quiet",
+            "--message-format=json-render-diagnostics",
+            "--bin",
+            &day.to_string(),
+        ]);
         if is_release {
-            args.push("--release");
+            cmd.arg("--release");
         }
+        let output = cmd.args(feature_args()).output()?;
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        if !output.status.success() {
+            return Err(Error::Build(
+                String::from_utf8_lossy(&output.stderr).into_owned(),
+            ));
         }
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(executable_of_artifact)
+            .next_back()
+            .ok_or_else(|| Error::Protocol(format!("cargo did not report an executable for {day}")))
+    }
+
+    /// Reads the executable from a `compiler-artifact` message of `cargo build --message-format=json`.
+    fn executable_of_artifact(message: &str) -> Option<PathBuf> {
+        let json: JsonValue = message.parse().ok()?;
+        let executable = json
+            .get::<HashMap<String, JsonValue>>()?
+            .get("executable")?;
+        executable.get::<String>().map(PathBuf::from)
+    }
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+    /// Runs `cmd` with [`REPORT_FILE_ENV`] set and collects the reports written by the child.
+    /// If `timeout` is set, the child is killed once a step runs longer than that.
+    fn run_with_reports(
+        mut cmd: Command,
+        day: PuzzleId,
+        capture: bool,
+        timeout: Option<Duration>,
+    ) -> Result<DayRun, Error> {
+        // the child writes machine-readable reports to this file.
+        let report_path =
+            env::temp_dir().join(format!("aoc-report-{}-{day}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&report_path);
+
+        cmd.env(REPORT_FILE_ENV, &report_path);
+
+        if capture {
+            cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+        } else {
+            cmd.stdout(Stdio::inherit()).stderr(Stdio::inherit());
+        }
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+        let mut child = cmd.spawn()?;
 
-        let mut output = vec![];
+        // drain the pipes while waiting, a child blocked on a full pipe would never exit.
+        let stdout = child.stdout.take().map(read_in_background);
+        let stderr = child.stderr.take().map(read_in_background);
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
+        let (exited, killed_after) = match timeout {
+            Some(timeout) => {
+                let (exited, killed) = wait_with_timeout(&mut child, &report_path, timeout)?;
+                (exited, killed.then_some(timeout))
+            }
+            None => (reap_blocking(&mut child)?, None),
+        };
+
+        let join = |handle: Option<thread::JoinHandle<String>>| {
+            handle
+                .and_then(|handle| handle.join().ok())
+                .unwrap_or_default()
+        };
+        let (stdout, stderr) = (join(stdout), join(stderr));
+
+        let events = read_events(&report_path).map_err(Error::Protocol);
+        let _ = fs::remove_file(&report_path);
+        let events = events?;
+
+        let timed_out = killed_after.map(|after| TimedOut {
+            step: unfinished_step(&events),
+            after,
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
-        }
+        let mut reports = vec![];
+        let mut input_mismatches = vec![];
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        for event in events {
+            match event {
+                Event::Finished(report) => reports.push(*report),
+                Event::InputMismatch(mismatch) => input_mismatches.push(mismatch),
+                Event::Started { .. } | Event::Benching { .. } => {}
+            }
+        }
 
-        Ok(output)
+        Ok(DayRun {
+            reports: Some(reports),
+            input_mismatches,
+            timed_out,
+            stdout,
+            stderr,
+            cpu_time: exited.cpu_time,
+        })
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
+    fn read_in_background(mut pipe: impl Read + Send + 'static) -> thread::JoinHandle<String> {
+        thread::spawn(move || {
+            let mut buf = vec![];
+            let _ = pipe.read_to_end(&mut buf);
+            String::from_utf8_lossy(&buf).into_owned()
+        })
+    }
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+    fn reap_blocking(child: &mut Child) -> io::Result<Exited> {
+        cpu_time::reap(child, true)?.ok_or_else(|| io::Error::other("child was not reaped"))
+    }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+    /// Waits for the child to exit and kills it if no step started or finished within `timeout`. While a step is
+    /// benched, the timeout is paused: the first execution of the step already completed in time, and warmup and
+    /// sampling are bounded by the bench budget.
+    /// Returns whether the child was killed.
+    fn wait_with_timeout(
+        child: &mut Child,
+        report_path: &Path,
+        timeout: Duration,
+    ) -> io::Result<(Exited, bool)> {
+        let mut deadline = Some(Instant::now() + timeout);
+        let mut report_len = 0;
+
+        loop {
+            if let Some(exited) = cpu_time::reap(child, false)? {
+                return Ok((exited, false));
+            }
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+            // every event written to the report file marks the start or end of a step, which restarts the timeout.
+            let len = fs::metadata(report_path).map_or(0, |x| x.len());
+            if len != report_len {
+                report_len = len;
+                let events = read_events(report_path).unwrap_or_default();
+                deadline = (!is_benching(&events)).then(|| Instant::now() + timeout);
+            }
 
-                timings.total_nanos += nanos;
-            });
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                let _ = child.kill();
+                return Ok((reap_blocking(child)?, true));
+            }
 
-        timings
+            thread::sleep(Duration::from_millis(10));
+        }
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    /// Whether the last event of a child is the start of benching a step.
+    fn is_benching(events: &[Event]) -> bool {
+        matches!(events.last(), Some(Event::Benching { .. }))
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .next_back()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+    /// Run a day in a child process, either passing its console output through or buffering it (`capture`).
+    /// The returned run has no reports if the day has not been scaffolded yet.
+    pub fn run_day(
+        day: PuzzleId,
+        options: &RunMultiOptions,
+        solutions: &[Solution],
+        capture: bool,
+    ) -> DayRun {
+        let is_scaffolded = if solutions.is_empty() {
+            Path::new(&get_path_for_bin(day)).exists()
+        } else {
+            solutions.iter().any(|s| s.puzzle == day)
+        };
+
+        let failed = |stderr: String| DayRun {
+            reports: None,
+            input_mismatches: vec![],
+            timed_out: None,
+            stdout: String::new(),
+            stderr,
+            cpu_time: None,
+        };
+
+        // skip command invocation for days that have not been scaffolded yet.
+        if !is_scaffolded {
+            return failed(String::new());
+        }
+
+        let timeout = options
+            .timeout
+            .map(|timeout| timeout_override(day).unwrap_or(timeout));
+
+        build_command(day, options, solutions)
+            .and_then(|cmd| run_with_reports(cmd, day, capture, timeout))
+            .unwrap_or_else(|e| DayRun {
+                reports: Some(vec![]),
+                ..failed(match e {
+                    Error::Build(output) => output,
+                    e => format!("Failed to run day {day}: {e:?}\n"),
+                })
+            })
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    /// Collects the reports of a day into a [`Timing`]. Steps without benchmark samples, unsolved parts and errored
+    /// steps do not count towards the timings, errored steps are listed separately.
+    pub fn timing_from_reports(reports: &[Report], puzzle: PuzzleId) -> Timing {
+        let mut timing = Timing {
+            puzzle,
+            parse: None,
+            part_1: None,
+            part_2: None,
+            total_nanos: 0_f64,
+            timed_out: false,
+            errored: vec![],
+            bench: None,
+        };
+
+        timing.errored = reports
+            .iter()
+            .filter(|r| r.puzzle == puzzle && r.error.is_some())
+            .map(|r| r.step)
+            .collect();
+
+        for report in reports
+            .iter()
+            .filter(|r| r.puzzle == puzzle && r.stats.samples > 1 && r.error.is_none())
+        {
+            let part_timing = PartTiming {
+                duration: format!("{:.1?}", report.stats.median),
+                stats: Some(report.stats),
+                memory: report.memory,
+                counters: report.counters,
+            };
+
+            if timing.bench.is_none() {
+                timing.bench = report.bench;
+            }
+
+            match report.step {
+                Step::Part(_) if report.answer.is_none() => continue,
+                Step::Parse => timing.parse = Some(part_timing),
+                Step::Part(1) => timing.part_1 = Some(part_timing),
+                Step::Part(2) => timing.part_2 = Some(part_timing),
+                Step::Part(_) => continue,
+            }
+
+            #[allow(clippy::cast_precision_loss)]
+            {
+                timing.total_nanos += report.stats.median.as_nanos() as f64;
+            }
+        }
+
+        timing
     }
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
+        use super::{executable_of_artifact, is_benching, timing_from_reports};
+        use crate::{
+            day,
+            template::{
+                PuzzleId,
+                protocol::{Event, Report, Step},
+                runner::BenchStats,
+            },
+            year,
+        };
+        use std::time::Duration;
+
+        fn report(step: Step, answer: Option<&str>, samples: &[u64]) -> Report {
+            let samples: Vec<Duration> =
+                samples.iter().copied().map(Duration::from_micros).collect();
+
+            Report {
+                puzzle: PuzzleId::new(year!(2025), day!(1)),
+                step,
+                answer: answer.map(Into::into),
+                error: None,
+                stats: BenchStats::from_samples(&samples),
+                memory: None,
+                counters: None,
+                bench: None,
+            }
+        }
 
         #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
+        fn collects_benched_parts() {
+            let res = timing_from_reports(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    report(Step::Parse, None, &[10, 10, 10]),
+                    report(Step::Part(1), Some("42"), &[70, 80, 90]),
+                    report(Step::Part(2), Some("line\nbreak"), &[100, 100]),
                 ],
-                day!(1),
+                PuzzleId::new(year!(2025), day!(1)),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.total_nanos, 190000_f64);
+            assert_eq!(res.parse.unwrap().duration, "10.0µs");
+            assert_eq!(res.part_1.as_ref().unwrap().duration, "80.0µs");
+            assert_eq!(res.part_1.unwrap().stats.unwrap().samples, 3);
+            assert_eq!(res.part_2.unwrap().duration, "100.0µs");
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
+        fn ignores_parts_that_were_not_benched() {
+            let res = timing_from_reports(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    report(Step::Part(1), None, &[10]),
+                    report(Step::Part(2), Some("1"), &[10]),
                 ],
-                day!(1),
+                PuzzleId::new(year!(2025), day!(1)),
             );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.total_nanos, 0_f64);
+            assert_eq!(res.part_1.is_none(), true);
+            assert_eq!(res.part_2.is_none(), true);
         }
 
         #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
+        fn ignores_unsolved_parts() {
+            let res = timing_from_reports(
                 &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
+                    report(Step::Parse, None, &[10, 10]),
+                    report(Step::Part(1), None, &[10, 10]),
                 ],
-                day!(1),
+                PuzzleId::new(year!(2025), day!(1)),
             );
-            assert_approx_eq!(res.total_nanos, 0_f64);
+            assert_eq!(res.total_nanos, 10000_f64);
+            assert_eq!(res.parse.is_some(), true);
             assert_eq!(res.part_1.is_none(), true);
+        }
+
+        #[test]
+        fn lists_errored_steps() {
+            let mut errored = report(Step::Part(2), None, &[10, 10]);
+            errored.error = Some("invalid grid".into());
+
+            let res = timing_from_reports(
+                &[report(Step::Part(1), Some("1"), &[10, 10]), errored],
+                PuzzleId::new(year!(2025), day!(1)),
+            );
+            assert_eq!(res.errored, vec![Step::Part(2)]);
             assert_eq!(res.part_2.is_none(), true);
+            assert_eq!(res.total_nanos, 10000_f64);
+        }
+
+        #[test]
+        fn ignores_reports_of_other_days() {
+            let mut other = report(Step::Part(1), Some("1"), &[10, 10]);
+            other.puzzle.day = day!(2);
+            let mut other_year = report(Step::Part(1), Some("1"), &[10, 10]);
+            other_year.puzzle.year = year!(2024);
+
+            let res =
+                timing_from_reports(&[other, other_year], PuzzleId::new(year!(2025), day!(1)));
+            assert_eq!(res.part_1.is_none(), true);
+        }
+
+        #[test]
+        fn pauses_timeout_while_benching() {
+            let puzzle = PuzzleId::new(year!(2025), day!(1));
+            let started = Event::Started {
+                puzzle,
+                step: Step::Part(1),
+            };
+            let benching = Event::Benching {
+                puzzle,
+                step: Step::Part(1),
+            };
+            let finished = Event::Finished(Box::new(report(Step::Part(1), Some("1"), &[10])));
+
+            assert!(!is_benching(&[]));
+            assert!(!is_benching(&[started.clone()]));
+            assert!(is_benching(&[started.clone(), benching.clone()]));
+            assert!(!is_benching(&[
+                started.clone(),
+                benching,
+                finished,
+                started
+            ]));
+        }
+
+        #[test]
+        fn reads_executables_of_artifacts() {
+            let artifact =
+                r#"{"reason":"compiler-artifact","executable":"/target/release/2025-01"}"#;
+            assert_eq!(
+                executable_of_artifact(artifact),
+                Some("/target/release/2025-01".into())
+            );
+
+            let library = r#"{"reason":"compiler-artifact","executable":null}"#;
+            assert_eq!(executable_of_artifact(library), None);
+            assert_eq!(
+                executable_of_artifact(r#"{"reason":"build-finished"}"#),
+                None
+            );
         }
     }
 }
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{DayRun, run_parallel};
+    use crate::{day, template::PuzzleId, year};
+    use std::{thread, time::Duration};
+
+    #[test]
+    fn run_parallel_reports_in_day_order() {
+        let days = [day!(1), day!(2), day!(3), day!(4)].map(|x| PuzzleId::new(year!(2025), x));
+        let mut order = vec![];
+
+        run_parallel(
+            &days,
+            4,
+            |day| {
+                // earlier days finish last.
+                thread::sleep(Duration::from_millis(
+                    u64::from(10 - day.day.into_inner()) * 5,
+                ));
+                DayRun {
+                    reports: None,
+                    input_mismatches: vec![],
+                    timed_out: None,
+                    stdout: day.to_string(),
+                    stderr: String::new(),
+                    cpu_time: None,
+                }
+            },
+            |index, day, run| {
+                assert_eq!(run.stdout, day.to_string());
+                order.push((index, day.day));
+            },
+        );
+
+        assert_eq!(
+            order,
+            vec![(0, day!(1)), (1, day!(2)), (2, day!(3)), (3, day!(4))]
+        );
+    }
+}
@@ -1,35 +1,344 @@
 /// Encapsulates code that interacts with solution functions.
+use std::error::Error;
 use std::fmt::Display;
 use std::hint::black_box;
-use std::io::{Write, stdout};
-use std::process::Output;
+use std::io::{Read, Write, stdin, stdout};
+use std::path::PathBuf;
+use std::sync::Once;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, fs, process};
 
 use crate::template::ANSI_BOLD;
-use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, aoc_cli};
+use crate::template::bench::{BenchConfig, Warmup};
+use crate::template::memory::{Measurement, MemoryStats};
+use crate::template::outcome::{PartOutcome, PartResult, render_error};
+use crate::template::perf::{Counters, PerfCounters};
+use crate::template::protocol::{InputMismatch, Report, Step};
+use crate::template::submissions::{self, Bounds, Submission};
+use crate::template::{
+    ANSI_ITALIC, ANSI_RED, ANSI_RESET, PuzzleId, answers,
+    backend::{self, SubmissionOutcome},
+    ocr, read_file,
+};
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Error for `--input` and `--example` passed together, only one input can be selected.
+pub const INPUT_CONFLICT: &str = "--input and --example cannot be combined, pass only one of them.";
+
+/// The input a solution is run against.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The puzzle input in `data/<year>/inputs`.
+    #[default]
+    Puzzle,
+    /// The example input in `data/<year>/examples` (`--example`).
+    Example,
+    /// Any other file (`--input <path>`).
+    File(PathBuf),
+    /// The standard input (`--input -`).
+    Stdin,
+}
+
+impl InputSource {
+    /// Parses the value of `--input`, where `-` stands for the standard input.
+    #[must_use]
+    pub fn from_arg(arg: &str) -> Self {
+        match arg {
+            "-" => Self::Stdin,
+            path => Self::File(PathBuf::from(path)),
+        }
+    }
+
+    /// Returns the command-line arguments that select this input in a solution binary.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            Self::Puzzle => vec![],
+            Self::Example => vec!["--example".into()],
+            Self::File(path) => vec!["--input".into(), path.display().to_string()],
+            Self::Stdin => vec!["--input".into(), "-".into()],
+        }
+    }
+
+    /// Whether answers for this input can be checked against `data/<year>/answers.json` and submitted.
+    #[must_use]
+    pub fn is_puzzle(&self) -> bool {
+        *self == Self::Puzzle
+    }
+
+    /// Reads the input of a puzzle. Exits if a file or the standard input cannot be read.
+    #[must_use]
+    pub fn read(&self, puzzle: PuzzleId) -> String {
+        let result = match self {
+            Self::Puzzle => return read_file("inputs", puzzle),
+            Self::Example => return read_file("examples", puzzle),
+            Self::File(path) => {
+                fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))
+            }
+            Self::Stdin => {
+                let mut input = String::new();
+                stdin()
+                    .read_to_string(&mut input)
+                    .map(|_| input)
+                    .map_err(|e| format!("standard input: {e}"))
+            }
+        };
+
+        result.unwrap_or_else(|e| {
+            eprintln!("Failed to read input from {e}");
+            process::exit(1);
+        })
+    }
+}
+
+/// Options that control how a solution is executed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RunOptions {
+    /// Bench settings, if the solution should be benched (`--time`).
+    pub time: Option<BenchConfig>,
+    /// The part that should be submitted (`--submit <part>`).
+    pub submit: Option<u8>,
+    /// The input to run against (`--input <path>`, `--input -` or `--example`).
+    pub input: InputSource,
+}
+
+impl RunOptions {
+    /// Applies the bench override of a puzzle, see [`BenchConfig::for_puzzle`].
+    #[must_use]
+    pub fn for_puzzle(&self, puzzle: PuzzleId) -> Self {
+        Self {
+            time: self.time.map(|config| config.for_puzzle(puzzle)),
+            ..self.clone()
+        }
+    }
+
+    /// Reads the options from the arguments passed to a solution binary.
+    pub fn from_env_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+
+        let time = args
+            .iter()
+            .any(|x| x == "--time")
+            .then(BenchConfig::from_env_args);
+
+        let submit = args.iter().position(|x| x == "--submit").map(|index| {
+            let Some(Ok(part)) = args.get(index + 1).map(|x| x.parse::<u8>()) else {
+                eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
+                process::exit(1);
+            };
+            part
+        });
+
+        let is_example = args.iter().any(|x| x == "--example");
+
+        let input = if let Some(index) = args.iter().position(|x| x == "--input") {
+            if is_example {
+                eprintln!("{INPUT_CONFLICT}");
+                process::exit(1);
+            }
+            let Some(arg) = args.get(index + 1) else {
+                eprintln!("Unexpected command-line input. Format: cargo solve 1 --input <path|->");
+                process::exit(1);
+            };
+            InputSource::from_arg(arg)
+        } else if is_example {
+            InputSource::Example
+        } else {
+            InputSource::Puzzle
+        };
+
+        let options = Self {
+            time,
+            submit,
+            input,
+        };
+        if let Err(e) = options.validate() {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+        options
+    }
+
+    /// Checks that the options can be combined.
+    ///
+    /// # Errors
+    /// Returns an error if an answer should be submitted for an input other than the puzzle input.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.submit.is_some() && !self.input.is_puzzle() {
+            return Err(
+                "Only answers for the puzzle input can be submitted, remove --submit.".into(),
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns the command-line arguments that reproduce these options in a solution binary.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = self.input.to_args();
+
+        if let Some(part) = self.submit {
+            args.extend(["--submit".into(), part.to_string()]);
+        }
+
+        if let Some(bench) = self.time {
+            args.push("--time".into());
+            args.extend(bench.to_args());
+        }
+
+        args
+    }
+}
+
+/// A solution compiled into the runner binary, see `build.rs`.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub puzzle: PuzzleId,
+    /// Runs the solution against the puzzle input, generated by the [`solution!`](crate::solution) macro.
+    pub run: fn(&RunOptions) -> RunOutput,
+}
+
+/// Everything a run of a solution produced, see [`Solution::run`].
+#[derive(Clone, Debug, Default)]
+pub struct RunOutput {
+    /// Reports of the steps run against the selected input.
+    pub reports: Vec<Report>,
+    /// Named inputs whose answers do not match, see [`named_inputs`](crate::template::named_inputs).
+    pub input_mismatches: Vec<InputMismatch>,
+}
+
+/// Run a single part of a solution and report its outcome. A part returns either an `Option` or a `Result`, see
+/// [`PartResult`].
+pub fn run_part<I: Copy, R: PartResult>(
+    func: impl Fn(I) -> R,
+    input: I,
+    puzzle: PuzzleId,
+    part: u8,
+    options: &RunOptions,
+) -> Report {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    Report::emit_started(puzzle, Step::Part(part));
+
+    let (result, measured) = run_timed(func, input, puzzle, Step::Part(part), options, |result| {
+        // errors are only rendered once the part is done, until then they show as unsolved.
+        let intermediate = result
+            .answer()
+            .map_or(PartOutcome::Unsolved, PartOutcome::Solved);
+        print_result(&intermediate, &part_str, "");
+    });
+
+    let outcome = result.into_outcome();
+    // answers drawn as letters are checked, reported and submitted as text.
+    let answer = outcome.answer().map(ocr::read);
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    // known answers only apply to the puzzle input.
+    let verdict = answer
+        .as_ref()
+        .filter(|_| options.input.is_puzzle())
+        .map(|x| format!(" {}", answers::check(puzzle, part, x)))
+        .unwrap_or_default();
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    print_result(&outcome, &part_str, &format!("{verdict}{measured}"));
+
+    let report = Report {
+        puzzle,
+        step: Step::Part(part),
+        answer: answer.as_ref().map(|x| x.to_string()),
+        error: outcome.error().map(Into::into),
+        stats: measured.stats,
+        memory: measured.memory,
+        counters: measured.counters,
+        bench: options.time,
+    };
+    report.emit();
+
+    if let Some(answer) = &answer
+        && submit_result(answer, puzzle, part, options) == Some(SubmissionOutcome::Correct)
+    {
+        answers::record(puzzle, part, answer);
     }
+
+    report
+}
+
+/// Run the parse step of a solution. It is timed the same way as a part and its output is shared by both parts.
+pub fn run_parse<I: Copy, P>(
+    func: impl Fn(I) -> P,
+    input: I,
+    puzzle: PuzzleId,
+    options: &RunOptions,
+) -> (P, Report) {
+    parse_step(func, input, puzzle, options, |_| None)
+}
+
+/// Run a parse step that may fail. A failure is printed with its sources and recorded in the report, see
+/// [`run_parse`].
+pub fn run_fallible_parse<I: Copy, P, E: Into<Box<dyn Error>>>(
+    func: impl Fn(I) -> Result<P, E>,
+    input: I,
+    puzzle: PuzzleId,
+    options: &RunOptions,
+) -> (Result<P, Box<dyn Error>>, Report) {
+    parse_step(
+        |input| func(input).map_err(Into::into),
+        input,
+        puzzle,
+        options,
+        |parsed| parsed.as_ref().err().map(|e| render_error(e.as_ref())),
+    )
+}
+
+fn parse_step<I: Copy, P>(
+    func: impl Fn(I) -> P,
+    input: I,
+    puzzle: PuzzleId,
+    options: &RunOptions,
+    error: impl Fn(&P) -> Option<String>,
+) -> (P, Report) {
+    Report::emit_started(puzzle, Step::Parse);
+
+    let (parsed, measured) = run_timed(func, input, puzzle, Step::Parse, options, |_| {
+        print!("Parse:");
+    });
+    let error = error(&parsed);
+
+    print!("\r");
+    match &error {
+        Some(error) => print_error("Parse", error, &measured.to_string()),
+        None => println!("Parse:{measured}"),
+    }
+
+    let report = Report {
+        puzzle,
+        step: Step::Parse,
+        answer: None,
+        error,
+        stats: measured.stats,
+        memory: measured.memory,
+        counters: measured.counters,
+        bench: options.time,
+    };
+    report.emit();
+
+    (parsed, report)
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///  2. in release, the function is benched (approx. the configured budget of execution time, 1 second by default,
+///     bounded by the minimum and maximum number of samples.)
+///
+/// When benching, a warmup phase precedes sampling and outliers are discarded from the samples, see [`BenchConfig`].
+/// Heap usage is recorded for the first execution only, see [`Measurement`].
 fn run_timed<I: Copy, T>(
     func: impl Fn(I) -> T,
     input: I,
+    puzzle: PuzzleId,
+    step: Step,
+    options: &RunOptions,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Measured) {
+    let measurement = Measurement::start();
     let timer = Instant::now();
     let result = {
         #[cfg(feature = "dhat-heap")]
@@ -38,65 +347,246 @@ fn run_timed<I: Copy, T>(
         func(input)
     };
     let base_time = timer.elapsed();
+    let memory = measurement.finish();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let (stats, counters) = if let Some(config) = &options.time {
+        Report::emit_benching(puzzle, step);
+        bench(func, input, &base_time, config)
     } else {
-        (base_time, 1)
+        (BenchStats::from_samples(&[base_time]), None)
     };
 
-    (result, run.0, run.1)
+    (
+        result,
+        Measured {
+            stats,
+            memory,
+            counters,
+        },
+    )
+}
+
+/// Everything that was measured for a single step.
+struct Measured {
+    stats: BenchStats,
+    memory: Option<MemoryStats>,
+    counters: Option<PerfCounters>,
+}
+
+/// Displays the duration, followed by the heap usage and hardware counters if available.
+impl Display for Measured {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format_duration(&self.stats))?;
+        if let Some(memory) = &self.memory {
+            write!(f, " [{memory}]")?;
+        }
+        if let Some(counters) = &self.counters {
+            write!(f, " [{counters}]")?;
+        }
+        Ok(())
+    }
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Copy, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    config: &BenchConfig,
+) -> (BenchStats, Option<PerfCounters>) {
     let mut stdout = stdout();
 
+    print!(" > {ANSI_ITALIC}warming up{ANSI_RESET}");
+    let _ = stdout.flush();
+
+    let estimate = warmup(&func, input, config.warmup).unwrap_or(*base_time);
+
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let bench_iterations = (config.budget.as_nanos() / cmp::max(estimate.as_nanos(), 10))
+        .clamp(config.min_samples.into(), config.max_samples.into());
 
     let mut timers: Vec<Duration> = vec![];
 
+    let counters = config.counters.then(open_counters).flatten();
+    if let Some(counters) = &counters {
+        counters.start();
+    }
+
     for _ in 0..bench_iterations {
         let timer = Instant::now();
         black_box(func(black_box(input)));
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    #[allow(clippy::cast_possible_truncation)]
+    let counters = counters.map(|x| x.stop(bench_iterations as u64));
+
+    let kept = config.outliers.apply(&timers);
+
+    let mut stats = BenchStats::from_samples(&kept);
+    stats.outliers = (timers.len() - kept.len()) as u128;
+    (stats, counters)
+}
+
+/// Opens the hardware counters, warning once if they are unavailable.
+fn open_counters() -> Option<Counters> {
+    static WARNING: Once = Once::new();
+
+    Counters::open()
+        .map_err(|e| {
+            WARNING.call_once(|| {
+                eprintln!("Hardware counters are unavailable, continuing without them: {e}")
+            });
+        })
+        .ok()
+}
+
+/// Executes the function until the warmup is exhausted and returns the fastest run, if any.
+fn warmup<I: Copy, T>(func: impl Fn(I) -> T, input: I, warmup: Warmup) -> Option<Duration> {
+    let start = Instant::now();
+    let mut fastest: Option<Duration> = None;
+    let mut iterations = 0;
+
+    loop {
+        let done = match warmup {
+            Warmup::Iterations(x) => iterations >= x,
+            Warmup::Time(x) => iterations > 0 && start.elapsed() >= x,
+        };
+
+        if done {
+            return fastest;
+        }
+
+        let timer = Instant::now();
+        black_box(func(black_box(input)));
+        let elapsed = timer.elapsed();
+
+        fastest = Some(fastest.map_or(elapsed, |x| x.min(elapsed)));
+        iterations += 1;
+    }
+}
+
+/// Summary statistics over the samples collected for a solution part.
+/// The median is used as the headline number since it is robust against outliers.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BenchStats {
+    pub samples: u128,
+    /// Number of samples that were discarded as outliers before computing the statistics.
+    pub outliers: u128,
+    pub mean: Duration,
+    pub median: Duration,
+    pub stddev: Duration,
+    pub min: Duration,
+    /// First quartile, 25% of the samples are faster.
+    pub q1: Duration,
+    /// Third quartile, 75% of the samples are faster.
+    pub q3: Duration,
+    pub max: Duration,
+    pub p95: Duration,
+}
+
+impl BenchStats {
+    /// Computes statistics for a non-empty set of samples.
+    ///
+    /// # Panics
+    /// Panics if `samples` is empty.
+    #[must_use]
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "expected at least one sample");
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let count = sorted.len();
+        let mean = average_duration(&sorted);
+
+        let median = if count.is_multiple_of(2) {
+            (sorted[count / 2 - 1] + sorted[count / 2]) / 2
+        } else {
+            sorted[count / 2]
+        };
+
+        #[allow(clippy::cast_precision_loss)]
+        let variance = if count > 1 {
+            let mean_nanos = mean.as_nanos() as f64;
+            sorted
+                .iter()
+                .map(|x| (x.as_nanos() as f64 - mean_nanos).powi(2))
+                .sum::<f64>()
+                / (count - 1) as f64
+        } else {
+            0_f64
+        };
+
+        // nearest-rank percentiles.
+        let percentile = |p: usize| sorted[(count * p).div_ceil(100).max(1) - 1];
+
+        Self {
+            samples: count as u128,
+            outliers: 0,
+            mean,
+            median,
+            stddev: Duration::from_secs_f64(variance.sqrt() / 1_000_000_000_f64),
+            min: sorted[0],
+            q1: percentile(25),
+            q3: percentile(75),
+            max: sorted[count - 1],
+            p95: percentile(95),
+        }
+    }
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
+/// Displays the secondary statistics, e.g. `mean 80.2µs, σ 5.1µs, min 70.0µs, max 200.0µs, p95 90.0µs`.
+impl Display for BenchStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "mean {:.1?}, σ {:.1?}, min {:.1?}, max {:.1?}, p95 {:.1?}",
+            self.mean, self.stddev, self.min, self.max, self.p95
+        )
+    }
+}
+
+fn average_duration(numbers: &[Duration]) -> Duration {
+    let total = numbers
         .iter()
         .map(std::time::Duration::as_nanos)
         .sum::<u128>()
-        / numbers.len() as u128
+        / numbers.len() as u128;
+
+    #[allow(clippy::cast_possible_truncation)]
+    Duration::from_nanos(total as u64)
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+fn format_duration(stats: &BenchStats) -> String {
+    if stats.samples == 1 {
+        format!(" ({:.1?})", stats.median)
+    } else if stats.outliers > 0 {
+        format!(
+            " ({:.1?} @ {} samples, {} outliers discarded; {stats})",
+            stats.median, stats.samples, stats.outliers
+        )
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(
+            " ({:.1?} @ {} samples; {stats})",
+            stats.median, stats.samples
+        )
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result(result: &PartOutcome, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
+        PartOutcome::Solved(result) => {
+            if result.contains('\n') {
+                let decoded = ocr::decode(result)
+                    .map(|x| format!("{ANSI_BOLD}{x}{ANSI_RESET} "))
+                    .unwrap_or_default();
+                let str = format!("{part}: {decoded}▼ {duration_str}");
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
@@ -114,7 +604,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        PartOutcome::Unsolved => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
@@ -122,46 +612,173 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 println!("{part}: ✖             ");
             }
         }
+        PartOutcome::Errored(error) => {
+            if is_intermediate_result {
+                print!("{part}: ✖");
+            } else {
+                print!("\r");
+                print_error(part, error, duration_str);
+            }
+        }
+    }
+}
+
+/// Prints an error in place of the result, with every source of the error indented on its own line.
+fn print_error(step: &str, error: &str, duration_str: &str) {
+    let mut lines = error.lines();
+    let message = lines.next().unwrap_or_default();
+
+    println!("{step}: {ANSI_RED}✖ {message}{ANSI_RESET}{duration_str}");
+    for line in lines {
+        println!("  {ANSI_RED}{line}{ANSI_RESET}");
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+/// Try to submit one part of the solution if:
+///  1. the part was selected with `--submit <part>`.
+///  2. a backend is available, see [`backend::select`].
+///  3. the answer is not ruled out by previous submissions, see [`submissions::guard`].
+///
+/// Prints the outcome of the submission, records it in the submission log and returns it.
 fn submit_result<T: Display>(
-    result: T,
-    day: Day,
+    result: &T,
+    puzzle: PuzzleId,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
-
-    if !args.contains(&"--submit".into()) {
+    options: &RunOptions,
+) -> Option<SubmissionOutcome> {
+    if options.submit != Some(part) {
         return None;
     }
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
+    let backend = backend::select().unwrap_or_else(|e| {
+        eprintln!("{e}");
         process::exit(1);
+    });
+
+    let answer = result.to_string();
+
+    let previous = submissions::read_part(puzzle, part).unwrap_or_else(|e| {
+        eprintln!("Failed to read the submission log: {e}");
+        vec![]
+    });
+
+    if let Err(refusal) = submissions::guard(&previous, &answer, submissions::now()) {
+        eprintln!("Refusing to submit {answer}: {refusal}");
+        return None;
     }
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    let bounds = Bounds::from_submissions(&previous);
+    if !bounds.is_empty() {
+        println!("Previous submissions show that the answer is {bounds}.");
+    }
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    println!("Submitting result via {}...", backend.name());
+    let outcome = match backend.submit(puzzle, part, &answer) {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            eprintln!("{e}");
+            return None;
+        }
     };
 
-    if part_submit != part {
-        return None;
+    println!("{}", outcome.feedback());
+
+    if submissions::is_logged(&outcome)
+        && let Err(e) = submissions::append(
+            puzzle.year,
+            &Submission::now(puzzle.day, part, &answer, outcome.clone()),
+        )
+    {
+        eprintln!("Failed to record the submission: {e}");
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!(
-            "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+    Some(outcome)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{BenchStats, InputSource, RunOptions};
+    use std::{path::PathBuf, time::Duration};
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_nanos).collect()
+    }
+
+    #[test]
+    fn computes_stats_for_odd_sample_count() {
+        let stats = BenchStats::from_samples(&nanos(&[50, 10, 30, 20, 40]));
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.mean, Duration::from_nanos(30));
+        assert_eq!(stats.median, Duration::from_nanos(30));
+        assert_eq!(stats.min, Duration::from_nanos(10));
+        assert_eq!(stats.max, Duration::from_nanos(50));
+        assert_eq!(stats.p95, Duration::from_nanos(50));
+        // sample standard deviation of [10, 20, 30, 40, 50] is ~15.81ns, rounded to the nearest nanosecond.
+        assert_eq!(stats.stddev, Duration::from_nanos(16));
+    }
+
+    #[test]
+    fn computes_median_for_even_sample_count() {
+        let stats = BenchStats::from_samples(&nanos(&[10, 20, 30, 1000]));
+        assert_eq!(stats.median, Duration::from_nanos(25));
+        assert_eq!(stats.mean, Duration::from_nanos(265));
+    }
+
+    #[test]
+    fn computes_percentiles_with_nearest_rank() {
+        let samples: Vec<u64> = (1..=100).collect();
+        let stats = BenchStats::from_samples(&nanos(&samples));
+        assert_eq!(stats.q1, Duration::from_nanos(25));
+        assert_eq!(stats.q3, Duration::from_nanos(75));
+        assert_eq!(stats.p95, Duration::from_nanos(95));
+    }
+
+    #[test]
+    fn handles_single_sample() {
+        let stats = BenchStats::from_samples(&nanos(&[42]));
+        assert_eq!(stats.samples, 1);
+        assert_eq!(stats.median, Duration::from_nanos(42));
+        assert_eq!(stats.stddev, Duration::ZERO);
+    }
+
+    #[test]
+    fn passes_the_input_to_solution_binaries() {
+        assert_eq!(InputSource::from_arg("-"), InputSource::Stdin);
+        assert_eq!(
+            InputSource::from_arg("other/01.txt"),
+            InputSource::File(PathBuf::from("other/01.txt"))
         );
-        process::exit(1);
+
+        let options = RunOptions {
+            time: None,
+            submit: None,
+            input: InputSource::from_arg("other/01.txt"),
+        };
+        assert_eq!(options.to_args(), ["--input", "other/01.txt"]);
+
+        let options = RunOptions {
+            submit: Some(1),
+            input: InputSource::Puzzle,
+            ..options
+        };
+        assert_eq!(options.to_args(), ["--submit", "1"]);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    #[test]
+    fn refuses_to_submit_answers_for_other_inputs() {
+        let options = RunOptions {
+            time: None,
+            submit: Some(2),
+            input: InputSource::Example,
+        };
+        assert!(options.validate().is_err());
+        assert!(
+            RunOptions {
+                input: InputSource::Puzzle,
+                ..options
+            }
+            .validate()
+            .is_ok()
+        );
+    }
 }
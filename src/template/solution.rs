@@ -0,0 +1,189 @@
+use std::error::Error;
+
+use crate::template::PuzzleId;
+use crate::template::outcome::{PartOutcome, PartResult, render_error};
+use crate::template::protocol::Report;
+use crate::template::runner::{RunOptions, run_fallible_parse, run_part};
+
+/// Typed alternative to the free `part_one` / `part_two` functions of a day.
+///
+/// A day implements the trait on a unit struct and passes it to the [`solution!`](crate::solution) macro. It is then
+/// run, benched and tested like any other day:
+///
+/// ```ignore
+/// advent_of_code::solution!(1, Day01);
+///
+/// pub struct Day01;
+///
+/// impl Solution for Day01 {
+///     type Input = Vec<u64>;
+///     type ParseError = std::num::ParseIntError;
+///     type PartOne = Option<u64>;
+///     type PartTwo = Result<String, std::fmt::Error>;
+///
+///     fn parse(input: &str) -> Result<Self::Input, Self::ParseError> {
+///         input.lines().map(str::parse).collect()
+///     }
+///
+///     fn part_one(input: &Self::Input) -> Self::PartOne {
+///         Some(input.iter().sum())
+///     }
+///
+///     fn part_two(input: &Self::Input) -> Self::PartTwo {
+///         Err(std::fmt::Error)
+///     }
+/// }
+/// ```
+pub trait Solution {
+    /// The parsed puzzle input, shared by both parts.
+    type Input;
+    /// Error of the parse step, rendered with its sources like the errors of the parts.
+    type ParseError: Into<Box<dyn Error>>;
+    /// Return type of part one, an `Option` or a `Result`, see [`PartResult`].
+    type PartOne: PartResult;
+    /// Return type of part two, an `Option` or a `Result`, see [`PartResult`].
+    type PartTwo: PartResult;
+
+    /// Parses the puzzle input. The parse step is timed separately from the parts.
+    ///
+    /// # Errors
+    /// Returns an error if the input is malformed. The parts are not run in that case.
+    fn parse(input: &str) -> Result<Self::Input, Self::ParseError>;
+
+    fn part_one(input: &Self::Input) -> Self::PartOne;
+
+    fn part_two(input: &Self::Input) -> Self::PartTwo;
+}
+
+/// Runs a day that implements [`Solution`] against its input. Used by the [`solution!`](crate::solution) macro.
+pub fn run<S: Solution>(puzzle: PuzzleId, input: &str, options: &RunOptions) -> Vec<Report> {
+    let (parsed, report) = run_fallible_parse(S::parse, input, puzzle, options);
+
+    match parsed {
+        Ok(parsed) => vec![
+            report,
+            run_part(S::part_one, &parsed, puzzle, 1, options),
+            run_part(S::part_two, &parsed, puzzle, 2, options),
+        ],
+        Err(_) => vec![report],
+    }
+}
+
+/// Solves a part of a day that implements [`Solution`] for an example input.
+pub fn solve_example<S: Solution>(part: u8, input: &str) -> PartOutcome {
+    let parsed = match S::parse(input) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            return PartOutcome::Errored(format!(
+                "failed to parse the example: {}",
+                render_error(e.into().as_ref())
+            ));
+        }
+    };
+
+    match part {
+        1 => S::part_one(&parsed).into_outcome(),
+        2 => S::part_two(&parsed).into_outcome(),
+        _ => PartOutcome::Unsolved,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Solution, solve_example};
+    use crate::template::outcome::PartOutcome;
+    use std::{error::Error, fmt::Display, num::ParseIntError};
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<u64>;
+        type ParseError = ParseIntError;
+        type PartOne = Option<u64>;
+        type PartTwo = Result<String, ParseIntError>;
+
+        fn parse(input: &str) -> Result<Self::Input, Self::ParseError> {
+            input.lines().map(str::parse).collect()
+        }
+
+        fn part_one(input: &Self::Input) -> Self::PartOne {
+            Some(input.iter().sum())
+        }
+
+        fn part_two(input: &Self::Input) -> Self::PartTwo {
+            let first = input.first().map_or("".parse::<u64>(), |x| Ok(*x))?;
+            Ok(format!("first is {first}"))
+        }
+    }
+
+    #[test]
+    fn solves_examples_with_different_answer_types() {
+        assert_eq!(
+            solve_example::<Sum>(1, "1\n2\n3"),
+            PartOutcome::Solved("6".into())
+        );
+        assert_eq!(
+            solve_example::<Sum>(2, "1\n2\n3"),
+            PartOutcome::Solved("first is 1".into())
+        );
+        assert_eq!(
+            solve_example::<Sum>(2, ""),
+            PartOutcome::Errored("cannot parse integer from empty string".into())
+        );
+    }
+
+    struct Grid;
+
+    #[derive(Debug)]
+    struct GridError(ParseIntError);
+
+    impl Display for GridError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("invalid grid")
+        }
+    }
+
+    impl Error for GridError {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    impl Solution for Grid {
+        type Input = u64;
+        type ParseError = GridError;
+        type PartOne = Option<u64>;
+        type PartTwo = Option<u64>;
+
+        fn parse(input: &str) -> Result<Self::Input, Self::ParseError> {
+            input.parse().map_err(GridError)
+        }
+
+        fn part_one(input: &Self::Input) -> Self::PartOne {
+            Some(*input)
+        }
+
+        fn part_two(_: &Self::Input) -> Self::PartTwo {
+            None
+        }
+    }
+
+    #[test]
+    fn fails_examples_that_do_not_parse() {
+        assert_eq!(
+            solve_example::<Sum>(1, "1\nx"),
+            PartOutcome::Errored(
+                "failed to parse the example: invalid digit found in string".into()
+            )
+        );
+        assert_eq!(
+            solve_example::<Grid>(1, "x"),
+            PartOutcome::Errored(
+                "failed to parse the example: invalid grid\ncaused by: invalid digit found in string"
+                    .into()
+            )
+        );
+    }
+}
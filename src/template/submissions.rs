@@ -0,0 +1,419 @@
+/// Log of every answer submitted to Advent of Code, used to avoid resubmitting answers that are known to be wrong.
+///
+/// Every wrong submission triggers a cooldown on the website, so before submitting, the answer is checked against
+/// the previous submissions of the part: answers that were rejected before, and numeric answers that are ruled out by
+/// a previous "too high" or "too low" response, are refused.
+///
+/// Every year has its own log, e.g. `data/2024/submissions.jsonl`. Every line of the log is a JSON object such as:
+///
+/// ```json
+/// {"day":"01","part":1,"answer":"142","outcome":"too_low","timestamp":1733011200}
+/// ```
+///
+/// Rate-limited submissions additionally store the time left to wait in `wait_seconds`, so that no answer is
+/// submitted before the cooldown is over.
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tinyjson::JsonValue;
+
+use crate::template::{Day, PuzzleId, Year, backend::SubmissionOutcome, data_dir};
+
+static SUBMISSIONS_FILE_NAME: &str = "submissions.jsonl";
+
+/// A single submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    /// Outcome of the submission, never [`SubmissionOutcome::Unrecognized`].
+    pub outcome: SubmissionOutcome,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+impl Submission {
+    #[must_use]
+    pub fn now(day: Day, part: u8, answer: &str, outcome: SubmissionOutcome) -> Self {
+        Self {
+            day,
+            part,
+            answer: answer.into(),
+            outcome,
+            timestamp: now(),
+        }
+    }
+}
+
+/// Seconds since the unix epoch.
+#[must_use]
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |x| x.as_secs())
+}
+
+/// The log of a year, e.g. `data/2024/submissions.jsonl`.
+#[must_use]
+pub fn path(year: Year) -> PathBuf {
+    data_dir(year).join(SUBMISSIONS_FILE_NAME)
+}
+
+/// Appends a submission to the log of a year.
+pub fn append(year: Year, submission: &Submission) -> Result<(), io::Error> {
+    fs::create_dir_all(data_dir(year))?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path(year))?;
+
+    let line = JsonValue::from(submission)
+        .stringify()
+        .map_err(io::Error::other)?;
+    writeln!(file, "{line}")
+}
+
+/// Reads all submissions of a part from the log, oldest first. If not present, returns no submissions.
+pub fn read_part(puzzle: PuzzleId, part: u8) -> Result<Vec<Submission>, String> {
+    let content = match fs::read_to_string(path(puzzle.year)) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e.to_string()),
+    };
+
+    let mut submissions = vec![];
+
+    for line in content.lines().filter(|line| !line.trim().is_empty()) {
+        let submission = Submission::from_str(line)?;
+        if submission.day == puzzle.day && submission.part == part {
+            submissions.push(submission);
+        }
+    }
+
+    Ok(submissions)
+}
+
+/// Range of numeric answers that previous "too high" and "too low" responses have not ruled out yet.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Bounds {
+    /// Largest answer that was too low, the correct answer is greater.
+    pub lower: Option<i128>,
+    /// Smallest answer that was too high, the correct answer is less.
+    pub upper: Option<i128>,
+}
+
+impl Bounds {
+    #[must_use]
+    pub fn from_submissions(submissions: &[Submission]) -> Self {
+        let numeric = |outcome: SubmissionOutcome| {
+            submissions
+                .iter()
+                .filter(move |x| x.outcome == outcome)
+                .filter_map(|x| x.answer.trim().parse::<i128>().ok())
+        };
+
+        Self {
+            lower: numeric(SubmissionOutcome::TooLow).max(),
+            upper: numeric(SubmissionOutcome::TooHigh).min(),
+        }
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.lower.is_none() && self.upper.is_none()
+    }
+
+    /// Whether a numeric answer lies within the bounds. Non-numeric answers cannot be ruled out.
+    #[must_use]
+    pub fn contains(&self, answer: &str) -> bool {
+        let Ok(value) = answer.trim().parse::<i128>() else {
+            return true;
+        };
+        self.lower.is_none_or(|x| value > x) && self.upper.is_none_or(|x| value < x)
+    }
+}
+
+/// Displays the bounds, e.g. `greater than 100 and less than 200`.
+impl Display for Bounds {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.lower, self.upper) {
+            (Some(lower), Some(upper)) => write!(f, "greater than {lower} and less than {upper}"),
+            (Some(lower), None) => write!(f, "greater than {lower}"),
+            (None, Some(upper)) => write!(f, "less than {upper}"),
+            (None, None) => f.write_str("unbounded"),
+        }
+    }
+}
+
+/// Reason for refusing to submit an answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Refusal {
+    /// The answer was submitted before and rejected.
+    KnownWrong(SubmissionOutcome),
+    /// The answer is ruled out by previous "too high" or "too low" responses.
+    OutOfBounds(Bounds),
+    /// The last submission was rate-limited and the cooldown is not over yet.
+    CoolingDown(Duration),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::KnownWrong(outcome) => {
+                write!(f, "this answer was already submitted and was {outcome}.")
+            }
+            Refusal::OutOfBounds(bounds) => {
+                write!(f, "previous submissions show that the answer is {bounds}.")
+            }
+            Refusal::CoolingDown(remaining) => {
+                write!(
+                    f,
+                    "the last submission was rate-limited, wait {remaining:?}."
+                )
+            }
+        }
+    }
+}
+
+/// Checks whether `answer` is worth submitting at `now` (seconds since the unix epoch), given the previous submissions
+/// of the part.
+pub fn guard(submissions: &[Submission], answer: &str, now: u64) -> Result<(), Refusal> {
+    if let Some(previous) = submissions
+        .iter()
+        .find(|x| x.outcome.is_wrong() && x.answer == answer)
+    {
+        return Err(Refusal::KnownWrong(previous.outcome.clone()));
+    }
+
+    let bounds = Bounds::from_submissions(submissions);
+    if !bounds.contains(answer) {
+        return Err(Refusal::OutOfBounds(bounds));
+    }
+
+    if let Some(Submission {
+        outcome: SubmissionOutcome::RateLimited { wait: Some(wait) },
+        timestamp,
+        ..
+    }) = submissions.last()
+    {
+        let ready_at = timestamp + wait.as_secs();
+        if ready_at > now {
+            return Err(Refusal::CoolingDown(Duration::from_secs(ready_at - now)));
+        }
+    }
+
+    Ok(())
+}
+
+/// Key of an outcome in the log, `None` for outcomes that are not logged.
+fn outcome_key(outcome: &SubmissionOutcome) -> Option<&'static str> {
+    match outcome {
+        SubmissionOutcome::Correct => Some("correct"),
+        SubmissionOutcome::Wrong => Some("wrong"),
+        SubmissionOutcome::TooHigh => Some("too_high"),
+        SubmissionOutcome::TooLow => Some("too_low"),
+        SubmissionOutcome::RateLimited { .. } => Some("rate_limited"),
+        SubmissionOutcome::WrongLevel => Some("wrong_level"),
+        SubmissionOutcome::Unrecognized(_) => None,
+    }
+}
+
+/// Whether an outcome is recorded in the log. Unrecognized responses are not.
+#[must_use]
+pub fn is_logged(outcome: &SubmissionOutcome) -> bool {
+    outcome_key(outcome).is_some()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(value.part.into()));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "outcome".into(),
+            JsonValue::String(outcome_key(&value.outcome).unwrap_or("unrecognized").into()),
+        );
+
+        if let SubmissionOutcome::RateLimited { wait: Some(wait) } = value.outcome {
+            #[allow(clippy::cast_precision_loss)]
+            map.insert(
+                "wait_seconds".into(),
+                JsonValue::Number(wait.as_secs() as f64),
+            );
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl FromStr for Submission {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = JsonValue::from_str(s).or(Err("submission is not valid JSON."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>())
+                .ok_or(format!("Expected submission.{key} to be a string."))
+        };
+
+        let day = string("day")?.parse::<Day>().map_err(|e| e.to_string())?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u8)
+            .ok_or("Expected submission.part to be a number.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u64)
+            .ok_or("Expected submission.timestamp to be a number.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let wait = json
+            .get("wait_seconds")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| Duration::from_secs(*x as u64));
+
+        let outcome = match string("outcome")?.as_str() {
+            "correct" => SubmissionOutcome::Correct,
+            "wrong" => SubmissionOutcome::Wrong,
+            "too_high" => SubmissionOutcome::TooHigh,
+            "too_low" => SubmissionOutcome::TooLow,
+            "rate_limited" => SubmissionOutcome::RateLimited { wait },
+            "wrong_level" => SubmissionOutcome::WrongLevel,
+            x => return Err(format!("unknown submission outcome \"{x}\"")),
+        };
+
+        Ok(Submission {
+            day,
+            part,
+            answer: string("answer")?.clone(),
+            outcome,
+            timestamp,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Bounds, Refusal, Submission, guard};
+    use crate::{day, template::backend::SubmissionOutcome};
+    use std::time::Duration;
+    use tinyjson::JsonValue;
+
+    const NOW: u64 = 1_733_011_200;
+
+    fn submission(answer: &str, outcome: SubmissionOutcome) -> Submission {
+        Submission {
+            day: day!(1),
+            part: 1,
+            answer: answer.into(),
+            outcome,
+            timestamp: NOW,
+        }
+    }
+
+    #[test]
+    fn round_trips_submissions() {
+        for outcome in [
+            SubmissionOutcome::TooLow,
+            SubmissionOutcome::RateLimited {
+                wait: Some(Duration::from_secs(38)),
+            },
+        ] {
+            let submission = submission("142", outcome);
+            let line = JsonValue::from(&submission).stringify().unwrap();
+            assert_eq!(line.parse::<Submission>().unwrap(), submission);
+        }
+    }
+
+    #[test]
+    fn computes_bounds() {
+        let submissions = [
+            submission("100", SubmissionOutcome::TooLow),
+            submission("120", SubmissionOutcome::TooLow),
+            submission("300", SubmissionOutcome::TooHigh),
+            submission("200", SubmissionOutcome::TooHigh),
+            submission("150", SubmissionOutcome::Wrong),
+        ];
+
+        let bounds = Bounds::from_submissions(&submissions);
+        assert_eq!(
+            bounds,
+            Bounds {
+                lower: Some(120),
+                upper: Some(200)
+            }
+        );
+        assert_eq!(bounds.to_string(), "greater than 120 and less than 200");
+        assert!(bounds.contains("121"));
+        assert!(!bounds.contains("120"));
+        assert!(!bounds.contains("200"));
+        assert!(bounds.contains("not a number"));
+    }
+
+    #[test]
+    fn guards_submissions() {
+        let submissions = [
+            submission("100", SubmissionOutcome::TooLow),
+            submission("150", SubmissionOutcome::Wrong),
+            submission("160", SubmissionOutcome::RateLimited { wait: None }),
+        ];
+
+        assert_eq!(
+            guard(&submissions, "150", NOW),
+            Err(Refusal::KnownWrong(SubmissionOutcome::Wrong))
+        );
+        assert!(matches!(
+            guard(&submissions, "99", NOW),
+            Err(Refusal::OutOfBounds(_))
+        ));
+        // rate-limited answers were never checked and may be submitted again.
+        assert_eq!(guard(&submissions, "160", NOW), Ok(()));
+        assert_eq!(guard(&submissions, "170", NOW), Ok(()));
+    }
+
+    #[test]
+    fn guards_cooldown() {
+        let submissions = [submission(
+            "160",
+            SubmissionOutcome::RateLimited {
+                wait: Some(Duration::from_secs(60)),
+            },
+        )];
+
+        assert_eq!(
+            guard(&submissions, "160", NOW + 20),
+            Err(Refusal::CoolingDown(Duration::from_secs(40)))
+        );
+        assert_eq!(guard(&submissions, "160", NOW + 60), Ok(()));
+    }
+}
@@ -1,17 +1,83 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fs, io::Error, str::FromStr, time::Duration};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{
+    Day, PuzzleId, Year,
+    bench::{BenchConfig, ExplicitSettings, format_duration, parse_duration},
+    memory::MemoryStats,
+    perf::PerfCounters,
+    protocol::Step,
+    runner::BenchStats,
+};
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 
-/// Represents benchmark times for a single day.
+/// Represents benchmark times for a single puzzle.
 #[derive(Clone, Debug)]
 pub struct Timing {
-    pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    pub puzzle: PuzzleId,
+    /// Timing of the optional parse step shared by both parts.
+    pub parse: Option<PartTiming>,
+    pub part_1: Option<PartTiming>,
+    pub part_2: Option<PartTiming>,
     pub total_nanos: f64,
+    /// Whether the day was killed because a step exceeded the timeout of `cargo time`.
+    pub timed_out: bool,
+    /// Steps that returned an error instead of an answer.
+    pub errored: Vec<Step>,
+    /// The settings the day was benched with, so that the timings can be reproduced.
+    pub bench: Option<BenchConfig>,
+}
+
+/// Represents the benchmark time of a single part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartTiming {
+    /// The headline duration as displayed, e.g. `74.1µs`. This is the median when benched.
+    pub duration: String,
+    /// Extended statistics, only present if the part was benched with more than one sample.
+    pub stats: Option<BenchStats>,
+    /// Heap usage, only present if the part was run with the `memory-stats` feature.
+    pub memory: Option<MemoryStats>,
+    /// Hardware counters per execution, only present if the part was benched with `--counters`.
+    pub counters: Option<PerfCounters>,
+}
+
+impl From<&str> for PartTiming {
+    fn from(value: &str) -> Self {
+        Self {
+            duration: value.into(),
+            stats: None,
+            memory: None,
+            counters: None,
+        }
+    }
+}
+
+impl PartTiming {
+    /// The median duration, read from the stats or parsed from the headline duration of older timings files.
+    pub fn median(&self) -> Option<Duration> {
+        self.stats
+            .map(|stats| stats.median)
+            .or_else(|| parse_duration_str(&self.duration))
+    }
+}
+
+/// Parses a duration as formatted by `Debug`, e.g. `74.1µs` or `1.2s`.
+fn parse_duration_str(s: &str) -> Option<Duration> {
+    let (value, nanos_per_unit) = [
+        ("ns", 1_f64),
+        ("µs", 1e3),
+        ("us", 1e3),
+        ("ms", 1e6),
+        ("s", 1e9),
+    ]
+    .into_iter()
+    .find_map(|(unit, factor)| s.strip_suffix(unit).map(|x| (x, factor)))?;
+
+    let nanos = value.trim().parse::<f64>().ok()? * nanos_per_unit;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    (nanos >= 0_f64).then(|| Duration::from_nanos(nanos.round() as u64))
 }
 
 /// Represents benchmark times for a set of days.
@@ -46,12 +112,12 @@ impl Timings {
         }
 
         for timing in &self.data {
-            if !data.iter().any(|t| t.day == timing.day) {
+            if !data.iter().any(|t| t.puzzle == timing.puzzle) {
                 data.push(timing.clone());
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.puzzle);
         Timings { data }
     }
 
@@ -60,10 +126,10 @@ impl Timings {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
-    pub fn is_day_complete(&self, day: Day) -> bool {
+    pub fn is_day_complete(&self, puzzle: PuzzleId) -> bool {
         self.data
             .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+            .any(|t| t.puzzle == puzzle && !t.timed_out && t.part_1.is_some() && t.part_2.is_some())
     }
 }
 
@@ -111,28 +177,72 @@ impl From<&Timing> for JsonValue {
     fn from(value: &Timing) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
-        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert(
+            "year".into(),
+            JsonValue::String(value.puzzle.year.to_string()),
+        );
+        map.insert(
+            "day".into(),
+            JsonValue::String(value.puzzle.day.to_string()),
+        );
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
-
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
-
+        map.insert("timed_out".into(), JsonValue::Boolean(value.timed_out));
         map.insert(
-            "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            "errored".into(),
+            JsonValue::Array(
+                value
+                    .errored
+                    .iter()
+                    .map(|step| JsonValue::String(step.to_string()))
+                    .collect(),
+            ),
         );
-
         map.insert(
-            "part_2".into(),
-            match part_2 {
-                Some(x) => x,
+            "bench".into(),
+            match &value.bench {
+                Some(x) => JsonValue::from(x),
                 None => JsonValue::Null,
             },
         );
 
+        for (key, part) in [
+            ("parse", &value.parse),
+            ("part_1", &value.part_1),
+            ("part_2", &value.part_2),
+        ] {
+            map.insert(
+                key.into(),
+                match part {
+                    Some(x) => JsonValue::String(x.duration.clone()),
+                    None => JsonValue::Null,
+                },
+            );
+
+            map.insert(
+                format!("{key}_stats"),
+                match part.as_ref().and_then(|x| x.stats.as_ref()) {
+                    Some(x) => JsonValue::from(x),
+                    None => JsonValue::Null,
+                },
+            );
+
+            map.insert(
+                format!("{key}_memory"),
+                match part.as_ref().and_then(|x| x.memory.as_ref()) {
+                    Some(x) => JsonValue::from(x),
+                    None => JsonValue::Null,
+                },
+            );
+
+            map.insert(
+                format!("{key}_counters"),
+                match part.as_ref().and_then(|x| x.counters.as_ref()) {
+                    Some(x) => JsonValue::from(x),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -151,26 +261,319 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
+        // NOTE: older timings files only hold the days of the default year.
+        let year = match json.get("year") {
+            None => Year::from_env()?,
+            Some(v) => v
+                .get::<String>()
+                .and_then(|year| Year::from_str(year).ok())
+                .ok_or("Expected timing.year to be a Year struct.")?,
+        };
 
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
+        // NOTE: older timings files do not have a parse step.
+        let parse = if json.contains_key("parse") {
+            part_timing_from_json(json, "parse")?
+        } else {
+            None
+        };
+        let part_1 = part_timing_from_json(json, "part_1")?;
+        let part_2 = part_timing_from_json(json, "part_2")?;
 
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: older timings files do not record timeouts.
+        let timed_out = match json.get("timed_out") {
+            None => false,
+            Some(v) => *v
+                .get::<bool>()
+                .ok_or("Expected timing.timed_out to be a boolean.")?,
+        };
+
+        // NOTE: older timings files do not record errors.
+        let errored = match json.get("errored") {
+            None => vec![],
+            Some(v) => v
+                .get::<Vec<JsonValue>>()
+                .and_then(|steps| {
+                    steps
+                        .iter()
+                        .map(|step| step.get::<String>()?.parse().ok())
+                        .collect()
+                })
+                .ok_or("Expected timing.errored to be an array of steps.")?,
+        };
+
+        // NOTE: older timings files do not record bench settings.
+        let bench = match json.get("bench") {
+            None | Some(JsonValue::Null) => None,
+            Some(v) => Some(BenchConfig::try_from(v)?),
+        };
+
         Ok(Timing {
-            day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
+            puzzle: PuzzleId::new(year, day),
+            parse,
+            part_1,
+            part_2,
             total_nanos,
+            timed_out,
+            errored,
+            bench,
+        })
+    }
+}
+
+/// Reads `key` and the optional `{key}_stats`, `{key}_memory` and `{key}_counters` siblings from a timing object.
+fn part_timing_from_json(
+    json: &HashMap<String, JsonValue>,
+    key: &str,
+) -> Result<Option<PartTiming>, String> {
+    let duration = json
+        .get(key)
+        .map(|v| if v.is_null() { None } else { v.get::<String>() })
+        .ok_or(format!("Expected timing.{key} to be null or string."))?;
+
+    let stats = match json.get(&format!("{key}_stats")) {
+        None | Some(JsonValue::Null) => None,
+        Some(v) => Some(BenchStats::try_from(v)?),
+    };
+
+    let memory = match json.get(&format!("{key}_memory")) {
+        None | Some(JsonValue::Null) => None,
+        Some(v) => Some(MemoryStats::try_from(v)?),
+    };
+
+    let counters = match json.get(&format!("{key}_counters")) {
+        None | Some(JsonValue::Null) => None,
+        Some(v) => Some(PerfCounters::try_from(v)?),
+    };
+
+    Ok(duration.map(|duration| PartTiming {
+        duration: duration.clone(),
+        stats,
+        memory,
+        counters,
+    }))
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&BenchStats> for JsonValue {
+    fn from(value: &BenchStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        let mut insert = |key: &str, nanos: u128| {
+            map.insert(key.into(), JsonValue::Number(nanos as f64));
+        };
+
+        insert("samples", value.samples);
+        insert("outliers", value.outliers);
+        insert("mean_nanos", value.mean.as_nanos());
+        insert("median_nanos", value.median.as_nanos());
+        insert("stddev_nanos", value.stddev.as_nanos());
+        insert("min_nanos", value.min.as_nanos());
+        insert("q1_nanos", value.q1.as_nanos());
+        insert("q3_nanos", value.q3.as_nanos());
+        insert("max_nanos", value.max.as_nanos());
+        insert("p95_nanos", value.p95.as_nanos());
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected timing stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let duration = |key: &str| number(key).map(|x| Duration::from_nanos(x as u64));
+
+        let median = duration("median_nanos")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(BenchStats {
+            samples: number("samples")? as u128,
+            // NOTE: older timings files do not record outliers.
+            outliers: number("outliers").unwrap_or_default() as u128,
+            mean: duration("mean_nanos")?,
+            median,
+            stddev: duration("stddev_nanos")?,
+            min: duration("min_nanos")?,
+            // NOTE: older timings files do not record quartiles, their box plots collapse onto the median.
+            q1: duration("q1_nanos").unwrap_or(median),
+            q3: duration("q3_nanos").unwrap_or(median),
+            max: duration("max_nanos")?,
+            p95: duration("p95_nanos")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&MemoryStats> for JsonValue {
+    fn from(value: &MemoryStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        let mut insert = |key: &str, value: u64| {
+            map.insert(key.into(), JsonValue::Number(value as f64));
+        };
+
+        insert("peak_bytes", value.peak_bytes);
+        insert("allocated_bytes", value.allocated_bytes);
+        insert("allocations", value.allocations);
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for MemoryStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected memory stats to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|x| x as u64)
+                .ok_or(format!("Expected memory.{key} to be a number."))
+        };
+
+        Ok(MemoryStats {
+            peak_bytes: number("peak_bytes")?,
+            allocated_bytes: number("allocated_bytes")?,
+            allocations: number("allocations")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&BenchConfig> for JsonValue {
+    fn from(value: &BenchConfig) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("warmup".into(), JsonValue::String(value.warmup.to_string()));
+        map.insert(
+            "outliers".into(),
+            JsonValue::String(value.outliers.to_string()),
+        );
+        map.insert(
+            "budget".into(),
+            JsonValue::String(format_duration(value.budget)),
+        );
+        map.insert(
+            "min_samples".into(),
+            JsonValue::Number(value.min_samples.into()),
+        );
+        map.insert(
+            "max_samples".into(),
+            JsonValue::Number(value.max_samples.into()),
+        );
+        map.insert("counters".into(), JsonValue::Boolean(value.counters));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchConfig {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected bench settings to be a JSON object.")?;
+
+        let string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>())
+                .ok_or(format!("Expected bench.{key} to be a string."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|x| *x as u32)
+                .ok_or(format!("Expected bench.{key} to be a number."))
+        };
+
+        Ok(BenchConfig {
+            warmup: string("warmup")?.parse().map_err(|e| format!("{e}"))?,
+            outliers: string("outliers")?.parse().map_err(|e| format!("{e}"))?,
+            budget: parse_duration(string("budget")?).map_err(|e| format!("{e}"))?,
+            min_samples: number("min_samples")?,
+            max_samples: number("max_samples")?,
+            counters: json
+                .get("counters")
+                .and_then(|v| v.get::<bool>())
+                .copied()
+                .unwrap_or_default(),
+            explicit: ExplicitSettings::default(),
+        })
+    }
+}
+
+impl From<&PerfCounters> for JsonValue {
+    fn from(value: &PerfCounters) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        let mut insert = |key: &str, value: Option<u64>| {
+            map.insert(
+                key.into(),
+                value.map_or(JsonValue::Null, |x| JsonValue::Number(x as f64)),
+            );
+        };
+
+        insert("cycles", value.cycles);
+        insert("instructions", value.instructions);
+        insert("branch_misses", value.branch_misses);
+        insert("cache_misses", value.cache_misses);
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PerfCounters {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected counters to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let number = |key: &str| match json.get(key) {
+            None | Some(JsonValue::Null) => Ok(None),
+            Some(v) => v
+                .get::<f64>()
+                .map(|x| Some(*x as u64))
+                .ok_or(format!("Expected counters.{key} to be null or a number.")),
+        };
+
+        Ok(PerfCounters {
+            cycles: number("cycles")?,
+            instructions: number("instructions")?,
+            branch_misses: number("branch_misses")?,
+            cache_misses: number("cache_misses")?,
         })
     }
 }
@@ -179,7 +582,7 @@ impl TryFrom<&JsonValue> for Timing {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use crate::day;
+    use crate::{day, template::PuzzleId, year};
 
     use super::{Timing, Timings};
 
@@ -187,29 +590,46 @@ mod tests {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
+                    puzzle: PuzzleId::new(year!(2025), day!(1)),
+                    parse: None,
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    timed_out: false,
+                    errored: vec![],
+                    bench: None,
                 },
                 Timing {
-                    day: day!(2),
+                    puzzle: PuzzleId::new(year!(2025), day!(2)),
+                    parse: None,
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    timed_out: false,
+                    errored: vec![],
+                    bench: None,
                 },
                 Timing {
-                    day: day!(4),
+                    puzzle: PuzzleId::new(year!(2025), day!(4)),
+                    parse: None,
                     part_1: Some("40ms".into()),
                     part_2: None,
                     total_nanos: 4e+10,
+                    timed_out: false,
+                    errored: vec![],
+                    bench: None,
                 },
             ],
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{
+            day,
+            template::{PuzzleId, protocol::Step, timings::Timings},
+            year,
+        };
+        use std::time::Duration;
 
         #[test]
         fn handles_json_timings() {
@@ -217,10 +637,72 @@ mod tests {
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
-            assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            assert_eq!(timing.puzzle.day, day!(1));
+            assert_eq!(timing.part_1, Some("1ms".into()));
             assert_eq!(timing.part_2, None);
+            assert_eq!(timing.parse, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.timed_out, false);
+        }
+
+        #[test]
+        fn handles_json_years() {
+            let json = r#"{ "data": [{ "year": "2024", "day": "01", "part_1": null, "part_2": null, "total_nanos": 0 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].puzzle, PuzzleId::new(year!(2024), day!(1)));
+
+            let json = r#"{ "data": [{ "year": "24", "day": "01", "part_1": null, "part_2": null, "total_nanos": 0 }] }"#.to_string();
+            assert!(Timings::try_from(json).is_err());
+        }
+
+        #[test]
+        fn handles_json_timed_out() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "timed_out": true }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].timed_out, true);
+        }
+
+        #[test]
+        fn handles_json_errored_steps() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": null, "part_2": null, "total_nanos": 0, "errored": ["parse", "2"] }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].errored, vec![Step::Parse, Step::Part(2)]);
+
+            let json = r#"{ "data": [{ "day": "01", "part_1": null, "part_2": null, "total_nanos": 0, "errored": ["3x"] }] }"#.to_string();
+            assert!(Timings::try_from(json).is_err());
+        }
+
+        #[test]
+        fn handles_json_parse_timing() {
+            let json = r#"{ "data": [{ "day": "01", "parse": "2ms", "part_1": "1ms", "part_2": null, "total_nanos": 3000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.parse, Some("2ms".into()));
+            assert_eq!(timing.part_1, Some("1ms".into()));
+        }
+
+        #[test]
+        fn handles_json_timing_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1.0ms", "part_1_stats": { "samples": 10, "mean_nanos": 1100000, "median_nanos": 1000000, "stddev_nanos": 5000, "min_nanos": 900000, "max_nanos": 2000000, "p95_nanos": 1500000 }, "part_2": null, "total_nanos": 1000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let part_1 = timings.data[0].part_1.as_ref().unwrap();
+            let stats = part_1.stats.unwrap();
+            assert_eq!(part_1.duration, "1.0ms");
+            assert_eq!(stats.samples, 10);
+            assert_eq!(stats.mean, Duration::from_micros(1100));
+            assert_eq!(stats.p95, Duration::from_micros(1500));
+            assert_eq!(stats.q1, stats.median);
+            assert_eq!(part_1.memory, None);
+        }
+
+        #[test]
+        fn handles_json_timing_memory() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1.0ms", "part_1_memory": { "peak_bytes": 2048, "allocated_bytes": 4096, "allocations": 7 }, "part_2": null, "total_nanos": 1000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let memory = timings.data[0].part_1.as_ref().unwrap().memory.unwrap();
+            assert_eq!(memory.peak_bytes, 2048);
+            assert_eq!(memory.allocated_bytes, 4096);
+            assert_eq!(memory.allocations, 7);
         }
 
         #[test]
@@ -247,7 +729,8 @@ mod tests {
 
     mod serialization {
         use super::get_mock_timings;
-        use std::collections::HashMap;
+        use crate::template::{bench::BenchConfig, runner::BenchStats, timings::Timings};
+        use std::{collections::HashMap, time::Duration};
         use tinyjson::JsonValue;
 
         #[test]
@@ -266,61 +749,146 @@ mod tests {
                 3
             );
         }
+
+        #[test]
+        fn round_trips_timing_stats() {
+            let mut timings = get_mock_timings();
+            let stats = BenchStats {
+                samples: 10,
+                outliers: 2,
+                mean: Duration::from_micros(12),
+                median: Duration::from_micros(10),
+                stddev: Duration::from_micros(1),
+                min: Duration::from_micros(9),
+                q1: Duration::from_micros(9),
+                q3: Duration::from_micros(11),
+                max: Duration::from_micros(30),
+                p95: Duration::from_micros(15),
+            };
+            timings.data[0].part_1.as_mut().unwrap().stats = Some(stats);
+
+            let json = JsonValue::from(timings).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data[0].puzzle, get_mock_timings().data[0].puzzle);
+            assert_eq!(parsed.data[0].part_1.as_ref().unwrap().stats, Some(stats));
+            assert_eq!(parsed.data[0].part_2.as_ref().unwrap().stats, None);
+        }
+
+        #[test]
+        fn round_trips_bench_settings() {
+            let mut timings = get_mock_timings();
+            let bench = BenchConfig {
+                budget: Duration::from_secs(5),
+                min_samples: 3,
+                max_samples: 20,
+                ..BenchConfig::default()
+            };
+            timings.data[0].bench = Some(bench);
+
+            let json = JsonValue::from(timings).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data[0].bench, Some(bench));
+            assert_eq!(parsed.data[1].bench, None);
+        }
+    }
+
+    mod median {
+        use crate::template::timings::PartTiming;
+        use std::time::Duration;
+
+        #[test]
+        fn parses_headline_durations() {
+            let median = |s: &str| PartTiming::from(s).median();
+            assert_eq!(median("500.0ns"), Some(Duration::from_nanos(500)));
+            assert_eq!(median("74.1µs"), Some(Duration::from_nanos(74_100)));
+            assert_eq!(median("1.5ms"), Some(Duration::from_micros(1_500)));
+            assert_eq!(median("2.0s"), Some(Duration::from_secs(2)));
+            assert_eq!(median("-"), None);
+        }
     }
 
     mod is_day_complete {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::{
+                PuzzleId,
+                timings::{Timing, Timings},
+            },
+            year,
         };
 
         #[test]
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
+                    puzzle: PuzzleId::new(year!(2025), day!(1)),
+                    parse: None,
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    timed_out: false,
+                    errored: vec![],
+                    bench: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(
+                timings.is_day_complete(PuzzleId::new(year!(2025), day!(1))),
+                true
+            );
         }
 
         #[test]
         fn handles_partial_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
+                    puzzle: PuzzleId::new(year!(2025), day!(1)),
+                    parse: None,
                     part_1: Some("1ms".into()),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    timed_out: false,
+                    errored: vec![],
+                    bench: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(
+                timings.is_day_complete(PuzzleId::new(year!(2025), day!(1))),
+                false
+            );
         }
 
         #[test]
         fn handles_uncompleted_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
+                    puzzle: PuzzleId::new(year!(2025), day!(1)),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    timed_out: false,
+                    errored: vec![],
+                    bench: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(
+                timings.is_day_complete(PuzzleId::new(year!(2025), day!(1))),
+                false
+            );
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::{
+                PuzzleId,
+                timings::{Timing, Timings},
+            },
+            year,
         };
 
         use super::get_mock_timings;
@@ -330,18 +898,22 @@ mod tests {
             let timings = get_mock_timings();
             let other = Timings {
                 data: vec![Timing {
-                    day: day!(3),
+                    puzzle: PuzzleId::new(year!(2025), day!(3)),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    timed_out: false,
+                    errored: vec![],
+                    bench: None,
                 }],
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
-            assert_eq!(merged.data[0].day, day!(1));
-            assert_eq!(merged.data[1].day, day!(2));
-            assert_eq!(merged.data[2].day, day!(3));
-            assert_eq!(merged.data[3].day, day!(4));
+            assert_eq!(merged.data[0].puzzle.day, day!(1));
+            assert_eq!(merged.data[1].puzzle.day, day!(2));
+            assert_eq!(merged.data[2].puzzle.day, day!(3));
+            assert_eq!(merged.data[3].puzzle.day, day!(4));
         }
 
         #[test]
@@ -350,19 +922,46 @@ mod tests {
 
             let other = Timings {
                 data: vec![Timing {
-                    day: day!(2),
+                    puzzle: PuzzleId::new(year!(2025), day!(2)),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    timed_out: false,
+                    errored: vec![],
+                    bench: None,
                 }],
             };
             let merged = timings.merge(&other);
 
             assert_eq!(merged.data.len(), 3);
-            assert_eq!(merged.data[0].day, day!(1));
-            assert_eq!(merged.data[1].day, day!(2));
+            assert_eq!(merged.data[0].puzzle.day, day!(1));
+            assert_eq!(merged.data[1].puzzle.day, day!(2));
             assert_eq!(merged.data[1].total_nanos, 0_f64);
-            assert_eq!(merged.data[2].day, day!(4));
+            assert_eq!(merged.data[2].puzzle.day, day!(4));
+        }
+
+        #[test]
+        fn keeps_timings_of_other_years() {
+            let timings = get_mock_timings();
+            let other = Timings {
+                data: vec![Timing {
+                    puzzle: PuzzleId::new(year!(2024), day!(2)),
+                    parse: None,
+                    part_1: None,
+                    part_2: None,
+                    total_nanos: 0_f64,
+                    timed_out: false,
+                    errored: vec![],
+                    bench: None,
+                }],
+            };
+            let merged = timings.merge(&other);
+
+            assert_eq!(merged.data.len(), 4);
+            assert_eq!(merged.data[0].puzzle, PuzzleId::new(year!(2024), day!(2)));
+            assert_eq!(merged.data[2].puzzle, PuzzleId::new(year!(2025), day!(2)));
+            assert_eq!(merged.data[2].total_nanos, 7e+10);
         }
 
         #[test]
@@ -0,0 +1,253 @@
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[cfg(feature = "today")]
+use chrono::{Datelike, FixedOffset, Utc};
+
+use crate::template::Day;
+
+/// Environment variable holding the year that commands apply to if no `--year` is passed, set in `.cargo/config.toml`.
+pub const YEAR_ENV: &str = "AOC_YEAR";
+
+#[cfg(feature = "today")]
+const SERVER_UTC_OFFSET: i32 = -5;
+
+/// A valid year of Advent of Code (i.e. 2015 or later).
+///
+/// # Display
+/// This value displays as a four digit number.
+///
+/// ```
+/// # use advent_of_code::template::Year;
+/// let year = Year::new(2024).unwrap();
+/// assert_eq!(year.to_string(), "2024")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's a year of Advent of Code, returns [`None`] otherwise.
+    pub const fn new(year: u16) -> Option<Self> {
+        if year < 2015 || year > 9999 {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// Reads the year from [`YEAR_ENV`].
+    ///
+    /// # Errors
+    /// Returns an error if the variable is not set or not a valid year.
+    pub fn from_env() -> Result<Self, String> {
+        let value = env::var(YEAR_ENV).map_err(|_| {
+            format!("{YEAR_ENV} is not set, pass --year or set it in .cargo/config.toml.")
+        })?;
+        value
+            .parse()
+            .map_err(|e| format!("{YEAR_ENV} is set to \"{value}\": {e}."))
+    }
+}
+
+#[cfg(feature = "today")]
+impl Year {
+    /// Returns the current year on the Advent of Code server.
+    pub fn today() -> Option<Self> {
+        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
+        let today = Utc::now().with_timezone(&offset);
+        Self::new(u16::try_from(today.year()).ok()?)
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year of Advent of Code, i.e. 2015 or later")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Identifies a puzzle by its year and day.
+///
+/// # Display
+/// This value displays as `<year>-<day>`, which is also the name of the solution binary and module file, e.g.
+/// `src/bin/2024-08.rs`.
+///
+/// ```
+/// # use advent_of_code::template::{Day, PuzzleId, Year};
+/// let puzzle = PuzzleId::new(Year::new(2024).unwrap(), Day::new(8).unwrap());
+/// assert_eq!(puzzle.to_string(), "2024-08")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl PuzzleId {
+    pub const fn new(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    /// Creates the [`PuzzleId`] of a solution from the path of its module file, `<year>-<day>.rs`. Used by the
+    /// [`solution!`](crate::solution) macro with `file!()`, so that the year of a solution is taken from its file name.
+    ///
+    /// # Panics
+    /// Panics if the file name does not follow the pattern or if its day differs from `day`. In a const context, this
+    /// is a compile error.
+    pub const fn from_module_path(path: &str, day: Day) -> Self {
+        let bytes = path.as_bytes();
+        let len = bytes.len();
+        // `YYYY-DD.rs` at the end of the path.
+        assert!(
+            len >= 10 && bytes[len - 6] == b'-' && bytes[len - 3] == b'.',
+            "expecting the solution file to be named <year>-<day>.rs, e.g. 2024-08.rs"
+        );
+
+        let mut year: u16 = 0;
+        let mut i = len - 10;
+        while i < len - 6 {
+            assert!(
+                bytes[i].is_ascii_digit(),
+                "expecting the solution file to be named <year>-<day>.rs, e.g. 2024-08.rs"
+            );
+            year = year * 10 + (bytes[i] - b'0') as u16;
+            i += 1;
+        }
+
+        assert!(
+            bytes[len - 5].is_ascii_digit() && bytes[len - 4].is_ascii_digit(),
+            "expecting the solution file to be named <year>-<day>.rs, e.g. 2024-08.rs"
+        );
+        let file_day = (bytes[len - 5] - b'0') * 10 + (bytes[len - 4] - b'0');
+        assert!(
+            file_day == day.into_inner(),
+            "the day of the solution! macro does not match the file name of the solution"
+        );
+
+        match Year::new(year) {
+            Some(year) => Self { year, day },
+            None => {
+                panic!("invalid year in the file name of the solution, expecting 2015 or later")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "today")]
+impl PuzzleId {
+    /// Returns the puzzle of the current day if it's between the 1st and the 25th of december, `None` otherwise.
+    pub fn today() -> Option<Self> {
+        Some(Self::new(Year::today()?, Day::today()?))
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.year, self.day)
+    }
+}
+
+impl FromStr for PuzzleId {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (year, day) = s
+            .split_once('-')
+            .ok_or(format!("expecting a puzzle as <year>-<day>, got \"{s}\""))?;
+        Ok(Self::new(
+            year.parse().map_err(|e| format!("{e}"))?,
+            day.parse().map_err(|e| format!("{e}"))?,
+        ))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {
+        const {
+            $crate::template::Year::new($year)
+                .expect("invalid year, expecting a year of Advent of Code, i.e. 2015 or later")
+        }
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{PuzzleId, Year};
+    use crate::day;
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2024".parse::<Year>().unwrap(), Year(2024));
+        assert!("2014".parse::<Year>().is_err());
+        assert!("24".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn formats_and_parses_puzzle_ids() {
+        let puzzle = PuzzleId::new(year!(2024), day!(8));
+        assert_eq!(puzzle.to_string(), "2024-08");
+        assert_eq!("2024-08".parse::<PuzzleId>(), Ok(puzzle));
+        assert!("2024".parse::<PuzzleId>().is_err());
+        assert!("2024-26".parse::<PuzzleId>().is_err());
+    }
+
+    #[test]
+    fn reads_puzzle_ids_from_module_paths() {
+        assert_eq!(
+            PuzzleId::from_module_path("src/bin/2024-08.rs", day!(8)),
+            PuzzleId::new(year!(2024), day!(8))
+        );
+        assert_eq!(
+            PuzzleId::from_module_path("/home/elf/aoc/src/bin/2015-25.rs", day!(25)),
+            PuzzleId::new(year!(2015), day!(25))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "does not match the file name")]
+    fn rejects_module_paths_of_other_days() {
+        let _ = PuzzleId::from_module_path("src/bin/2024-08.rs", day!(9));
+    }
+
+    #[test]
+    #[should_panic(expected = "<year>-<day>.rs")]
+    fn rejects_module_paths_without_year() {
+        let _ = PuzzleId::from_module_path("src/bin/08.rs", day!(8));
+    }
+}